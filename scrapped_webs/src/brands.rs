@@ -0,0 +1,231 @@
+//! Makeup brands known to be sold in the websites we scrap.
+//!
+//! Used to detect the brand inside a search and to compare brand names written in different ways.
+
+use crate::similarity;
+
+/// Groups of names for the same brand.
+/// The first name of every group is the canonical one, the rest are other ways of writing it.
+pub const KNOWN_BRANDS: &[&[&str]] = &[
+    &["Anastasia Beverly Hills", "ABH"],
+    &["Armani Beauty", "Giorgio Armani"],
+    &["Benefit Cosmetics", "Benefit"],
+    &["Bobbi Brown"],
+    &["Bourjois"],
+    &["Catrice"],
+    &["Chanel"],
+    &["Charlotte Tilbury"],
+    &["Clinique"],
+    &["Dior"],
+    &["e.l.f. Cosmetics", "e.l.f.", "elf"],
+    &["Essence"],
+    &["Estée Lauder"],
+    &["Fenty Beauty"],
+    &["Givenchy"],
+    &["Glossier"],
+    &["Guerlain"],
+    &["Hourglass"],
+    &["Huda Beauty"],
+    &["Inglot"],
+    &["Isdin"],
+    &["Kiko Milano", "Kiko"],
+    &["Kylie Cosmetics"],
+    &["L'Oréal Paris", "L'Oréal", "Loreal Paris", "Loreal"],
+    &["Lancôme"],
+    &["Laura Mercier"],
+    &["MAC Cosmetics", "MAC"],
+    &["Make Up For Ever", "MUFE"],
+    &["Makeup Revolution", "Revolution"],
+    &["Max Factor"],
+    &["Maybelline New York", "Maybelline"],
+    &["Milani"],
+    &["Morphe"],
+    &["NARS"],
+    &["Natasha Denona"],
+    &["NYX Professional Makeup", "NYX"],
+    &["Pat McGrath Labs", "Pat McGrath"],
+    &["Rare Beauty"],
+    &["Rimmel London", "Rimmel"],
+    &["Sephora Collection"],
+    &["Shiseido"],
+    &["Smashbox"],
+    &["Tarte"],
+    &["Too Faced"],
+    &["Urban Decay"],
+    &["Wet n Wild"],
+    &["Yves Saint Laurent", "YSL"],
+];
+
+/// Names of brands that are also common words or parts of other words, e.g. "essence serum" or "mascara revolution".
+/// They are only detected as brands when they are the whole text, the longer names of their brands anywhere.
+const GENERIC_NAMES: &[&str] = &["Benefit", "elf", "Essence", "MAC", "Revolution"];
+
+/// Looks for a known brand inside the text.
+///
+/// # Arguments
+/// text - The text to look into, usually the search provided by the user.
+///
+/// # Returns
+/// The canonical name of the brand found and the text without it, None if there is no brand.
+/// The names in `GENERIC_NAMES` are only found when they are the whole text.
+///
+/// # Example
+/// let (brand, name) = detect_brand("Rare Beauty colorete").unwrap();
+/// assert_eq!(brand, "Rare Beauty");
+/// assert_eq!(name, "colorete");
+pub fn detect_brand(text: &str) -> Option<(&'static str, String)> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let normalized_words: Vec<String> = words.iter().map(|w| similarity::normalize(w)).collect();
+
+    // (canonical name, position of the first word, number of words)
+    let mut best_match: Option<(&'static str, usize, usize)> = None;
+    for group in KNOWN_BRANDS {
+        for name in group.iter() {
+            if GENERIC_NAMES.contains(name) && words.len() > 1 {
+                continue;
+            }
+            let normalized_name = similarity::normalize(name);
+            let name_words: Vec<&str> = normalized_name.split(' ').collect();
+            let position = normalized_words
+                .windows(name_words.len())
+                .position(|window| window.iter().zip(&name_words).all(|(w, n)| w == n));
+            if let Some(position) = position {
+                // The longest name wins, so "Maybelline New York" is preferred over "Maybelline".
+                if best_match.is_none_or(|(_, _, length)| name_words.len() > length) {
+                    best_match = Some((group[0], position, name_words.len()));
+                }
+            }
+        }
+    }
+
+    best_match.map(|(brand, position, length)| {
+        let remaining = words
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| *index < position || *index >= position + length)
+            .map(|(_, word)| *word)
+            .collect::<Vec<&str>>()
+            .join(" ");
+        (brand, remaining)
+    })
+}
+
+/// Returns true if both names refer to the same brand.
+/// Names are compared without case and accents and using the known ways of writing every brand.
+///
+/// # Example
+/// assert!(same_brand("MAYBELLINE NEW YORK", "Maybelline"));
+pub fn same_brand(brand1: &str, brand2: &str) -> bool {
+    let brand1 = similarity::normalize(brand1);
+    let brand2 = similarity::normalize(brand2);
+    if brand1 == brand2 {
+        return true;
+    }
+    KNOWN_BRANDS.iter().any(|group| {
+        let names: Vec<String> = group
+            .iter()
+            .map(|name| similarity::normalize(name))
+            .collect();
+        names.contains(&brand1) && names.contains(&brand2)
+    })
+}
+
+/// Returns true if the brand is one of the brands requested and none of the brands excluded.
+/// Brands are compared as in `same_brand`, and without brands requested every brand not excluded is accepted.
+///
+/// # Arguments
+/// brand - The brand of the product, None if it is unknown.
+/// brands - The brands requested, empty to accept every brand.
+/// excluded_brands - The brands not wanted.
+///
+/// # Example
+/// assert!(is_accepted(Some("NYX Professional Makeup"), &[String::from("nyx")], &[]));
+pub fn is_accepted(brand: Option<&str>, brands: &[String], excluded_brands: &[String]) -> bool {
+    match brand {
+        Some(brand) => {
+            (brands.is_empty() || brands.iter().any(|requested| same_brand(brand, requested)))
+                && !excluded_brands
+                    .iter()
+                    .any(|excluded| same_brand(brand, excluded))
+        }
+        None => brands.is_empty(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests the detection of brands in different positions and ways of writing them.
+    #[test]
+    fn detect_brand_all_cases() {
+        assert_eq!(
+            detect_brand("Rare Beauty colorete"),
+            Some(("Rare Beauty", String::from("colorete")))
+        );
+        assert_eq!(
+            detect_brand("labial loreal  mate"),
+            Some(("L'Oréal Paris", String::from("labial mate")))
+        );
+        assert_eq!(
+            detect_brand("maybelline new york superstay"),
+            Some(("Maybelline New York", String::from("superstay")))
+        );
+        assert_eq!(
+            detect_brand("nyx"),
+            Some(("NYX Professional Makeup", String::new()))
+        );
+        assert_eq!(
+            detect_brand("Revolution"),
+            Some(("Makeup Revolution", String::new()))
+        );
+        assert_eq!(
+            detect_brand("makeup revolution mascara"),
+            Some(("Makeup Revolution", String::from("mascara")))
+        );
+        assert_eq!(
+            detect_brand("gimme brow benefit cosmetics"),
+            Some(("Benefit Cosmetics", String::from("gimme brow")))
+        );
+    }
+
+    /// Tests that only complete words are detected as brands.
+    #[test]
+    fn detect_brand_without_brand() {
+        assert_eq!(detect_brand("colorete"), None);
+        assert_eq!(detect_brand("macarena labial"), None);
+        assert_eq!(detect_brand("mascara revolution"), None);
+        assert_eq!(detect_brand("essence serum"), None);
+        assert_eq!(detect_brand("mac lipstick"), None);
+        assert_eq!(detect_brand(""), None);
+    }
+
+    /// Tests the comparison between brand names.
+    #[test]
+    fn same_brand_all_cases() {
+        assert!(same_brand("MAYBELLINE NEW YORK", "Maybelline"));
+        assert!(same_brand("Milani ", "milani"));
+        assert!(same_brand("L'OREAL PARIS", "L'Oréal Paris"));
+        assert!(same_brand("Unknown Brand", "unknown brand"));
+        assert!(!same_brand("Milani", "Maybelline"));
+        assert!(!same_brand("NYX", "MAC"));
+    }
+
+    /// Tests the brands accepted with brands requested and excluded.
+    #[test]
+    fn is_accepted_all_cases() {
+        let brands = vec![String::from("nyx"), String::from("L'OREAL")];
+        let excluded_brands = vec![String::from("lancome")];
+        assert!(is_accepted(Some("NYX Professional Makeup"), &brands, &[]));
+        assert!(is_accepted(
+            Some("L'Oréal Paris"),
+            &brands,
+            &excluded_brands
+        ));
+        assert!(!is_accepted(Some("Milani"), &brands, &[]));
+        assert!(!is_accepted(None, &brands, &[]));
+        assert!(is_accepted(Some("Milani"), &[], &excluded_brands));
+        assert!(!is_accepted(Some("Lancôme"), &[], &excluded_brands));
+        assert!(is_accepted(None, &[], &excluded_brands));
+    }
+}
@@ -0,0 +1,288 @@
+//! Swatch colours of the tones and how to paint them in the terminal.
+
+use std::io::IsTerminal;
+
+use scraper::ElementRef;
+use serde::{Serialize, Serializer};
+
+/// Attributes where the websites may store the colour of a swatch.
+const COLOUR_ATTRIBUTES: [&str; 3] = ["data-color", "data-colour", "data-hex"];
+
+/// A colour in RGB.
+#[derive(Clone, Copy, PartialEq, PartialOrd, Debug)]
+pub struct Rgb {
+    pub red: u8,
+    pub green: u8,
+    pub blue: u8,
+}
+
+/// How colours can be painted in the terminal.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ColourMode {
+    /// 24-bit colours.
+    TrueColour,
+    /// The 256 colours palette.
+    Ansi256,
+    /// No colours, emoji or escape sequences, only ASCII markers and the euro sign of the prices.
+    /// Used for CI logs, old terminals and screen readers.
+    Plain,
+}
+
+impl ColourMode {
+    /// Returns the colour mode supported by the standard output.
+    /// Plain if NO_COLOR is set or it is not a terminal, otherwise based on the COLORTERM variable.
+    pub fn detect() -> Self {
+        Self::from_env(
+            std::env::var_os("NO_COLOR").is_some() || !std::io::stdout().is_terminal(),
+            std::env::var("COLORTERM").ok().as_deref(),
+        )
+    }
+
+    /// Returns the colour mode for the values of the NO_COLOR and COLORTERM variables.
+    fn from_env(no_color: bool, colorterm: Option<&str>) -> Self {
+        match (no_color, colorterm) {
+            (true, _) => ColourMode::Plain,
+            (false, Some("truecolor" | "24bit")) => ColourMode::TrueColour,
+            (false, _) => ColourMode::Ansi256,
+        }
+    }
+}
+
+impl Rgb {
+    pub fn new(red: u8, green: u8, blue: u8) -> Self {
+        Self { red, green, blue }
+    }
+
+    /// Parses a CSS colour.
+    ///
+    /// # Arguments
+    /// text - The colour as #rgb, #rrggbb or rgb(r, g, b).
+    ///
+    /// # Returns
+    /// The colour or None if it is not a supported CSS colour.
+    ///
+    /// # Example
+    /// assert_eq!(Rgb::parse("#E0A0B0"), Some(Rgb::new(224, 160, 176)));
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim().to_lowercase();
+        if let Some(hex) = text.strip_prefix('#') {
+            if !hex.is_ascii() {
+                return None;
+            }
+            let channel = |hex: &str| u8::from_str_radix(hex, 16).ok();
+            return match hex.len() {
+                6 => Some(Self::new(
+                    channel(&hex[0..2])?,
+                    channel(&hex[2..4])?,
+                    channel(&hex[4..6])?,
+                )),
+                3 => Some(Self::new(
+                    channel(&hex[0..1])? * 17,
+                    channel(&hex[1..2])? * 17,
+                    channel(&hex[2..3])? * 17,
+                )),
+                _ => None,
+            };
+        }
+        let channels: Vec<u8> = text
+            .strip_prefix("rgba(")
+            .or_else(|| text.strip_prefix("rgb("))?
+            .strip_suffix(')')?
+            .split(',')
+            .take(3)
+            .map(|channel| channel.trim().parse::<u8>().ok())
+            .collect::<Option<Vec<u8>>>()?;
+        match channels[..] {
+            [red, green, blue] => Some(Self::new(red, green, blue)),
+            _ => None,
+        }
+    }
+
+    /// Looks for the colour of a swatch in an element or its descendants,
+    /// in its background in the style attribute or in a data attribute.
+    ///
+    /// # Arguments
+    /// element - The swatch or an element containing it.
+    ///
+    /// # Returns
+    /// The first colour found, None if there is no colour.
+    pub fn from_element(element: &ElementRef) -> Option<Self> {
+        std::iter::once(*element)
+            .chain(element.descendants().filter_map(ElementRef::wrap))
+            .find_map(|element| {
+                let attributes = element.value();
+                COLOUR_ATTRIBUTES
+                    .iter()
+                    .filter_map(|attribute| attributes.attr(attribute))
+                    .find_map(Self::parse)
+                    .or_else(|| attributes.attr("style").and_then(Self::from_style))
+            })
+    }
+
+    /// Returns the background colour of a style attribute.
+    fn from_style(style: &str) -> Option<Self> {
+        style
+            .split(';')
+            .filter_map(|declaration| declaration.split_once(':'))
+            .filter(|(property, _)| {
+                matches!(
+                    property.trim().to_lowercase().as_str(),
+                    "background" | "background-color"
+                )
+            })
+            .find_map(|(_, value)| {
+                // background may contain more values, e.g. "#fff url(...) no-repeat".
+                Self::parse(value).or_else(|| value.split_whitespace().find_map(Self::parse))
+            })
+    }
+
+    /// Returns the colour as #rrggbb.
+    pub fn to_hex(&self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.red, self.green, self.blue)
+    }
+
+    /// Returns the closest colour of the 256 colours palette, from the 6x6x6 cube or the grey ramp.
+    pub fn to_ansi256(&self) -> u8 {
+        const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+        let cube_index = |channel: u8| -> usize {
+            CUBE_LEVELS
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, level)| (**level as i32 - channel as i32).abs())
+                .map(|(index, _)| index)
+                .unwrap()
+        };
+        let (red, green, blue) = (
+            cube_index(self.red),
+            cube_index(self.green),
+            cube_index(self.blue),
+        );
+        let cube_colour = Self::new(CUBE_LEVELS[red], CUBE_LEVELS[green], CUBE_LEVELS[blue]);
+
+        let average = (self.red as u32 + self.green as u32 + self.blue as u32) / 3;
+        let grey_index = ((average.saturating_sub(8) + 5) / 10).min(23) as u8;
+        let grey_level = 8 + grey_index * 10;
+        let grey_colour = Self::new(grey_level, grey_level, grey_level);
+
+        match self.distance(&grey_colour) < self.distance(&cube_colour) {
+            true => 232 + grey_index,
+            false => (16 + 36 * red + 6 * green + blue) as u8,
+        }
+    }
+
+    /// Returns a block painted with the colour, empty if there are no colours.
+    pub fn swatch(&self, colour_mode: ColourMode) -> String {
+        let colour = match colour_mode {
+            ColourMode::TrueColour => ansi_term::Colour::RGB(self.red, self.green, self.blue),
+            ColourMode::Ansi256 => ansi_term::Colour::Fixed(self.to_ansi256()),
+            ColourMode::Plain => return String::new(),
+        };
+        colour.paint("██").to_string()
+    }
+
+    /// Returns the squared distance between two colours.
+    fn distance(&self, other: &Self) -> u32 {
+        let difference = |channel1: u8, channel2: u8| (channel1 as i32 - channel2 as i32).pow(2);
+        (difference(self.red, other.red)
+            + difference(self.green, other.green)
+            + difference(self.blue, other.blue)) as u32
+    }
+}
+
+impl Serialize for Rgb {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_hex())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests the CSS colours supported.
+    #[test]
+    fn parse_all_cases() {
+        assert_eq!(Rgb::parse("#E0A0B0"), Some(Rgb::new(224, 160, 176)));
+        assert_eq!(Rgb::parse(" #fff "), Some(Rgb::new(255, 255, 255)));
+        assert_eq!(Rgb::parse("rgb(10, 20, 30)"), Some(Rgb::new(10, 20, 30)));
+        assert_eq!(Rgb::parse("rgba(10,20,30,0.5)"), Some(Rgb::new(10, 20, 30)));
+        assert_eq!(Rgb::parse("#12345"), None);
+        assert_eq!(Rgb::parse("#gggggg"), None);
+        assert_eq!(Rgb::parse("#éa"), None);
+        assert_eq!(Rgb::parse("rgb(300, 0, 0)"), None);
+        assert_eq!(Rgb::parse("red"), None);
+    }
+
+    /// Tests the colour is found in the style or data attributes of the swatch.
+    #[test]
+    fn from_element_all_cases() {
+        let html = scraper::Html::parse_fragment(
+            r#"<li><a href="/tone"><span style="width: 10px; background-color: #c0506a"></span></a></li>"#,
+        );
+        assert_eq!(
+            Rgb::from_element(&html.root_element()),
+            Some(Rgb::new(192, 80, 106))
+        );
+
+        let html = scraper::Html::parse_fragment(
+            r#"<div style="background: url(swatch.png) #102030 no-repeat"></div>"#,
+        );
+        assert_eq!(
+            Rgb::from_element(&html.root_element()),
+            Some(Rgb::new(16, 32, 48))
+        );
+
+        let html = scraper::Html::parse_fragment(r##"<div><img data-color="#ABCDEF"></div>"##);
+        assert_eq!(
+            Rgb::from_element(&html.root_element()),
+            Some(Rgb::new(171, 205, 239))
+        );
+
+        let html = scraper::Html::parse_fragment(r#"<div style="color: #fff">01</div>"#);
+        assert_eq!(Rgb::from_element(&html.root_element()), None);
+    }
+
+    /// Tests the closest colour of the 256 colours palette.
+    #[test]
+    fn to_ansi256_all_cases() {
+        assert_eq!(Rgb::new(0, 0, 0).to_ansi256(), 16);
+        assert_eq!(Rgb::new(255, 255, 255).to_ansi256(), 231);
+        assert_eq!(Rgb::new(255, 0, 0).to_ansi256(), 196);
+        assert_eq!(Rgb::new(128, 128, 128).to_ansi256(), 244);
+        assert_eq!(Rgb::new(224, 160, 176).to_ansi256(), 181);
+    }
+
+    /// Tests the swatch in every colour mode.
+    #[test]
+    fn swatch_all_modes() {
+        let colour = Rgb::new(224, 160, 176);
+        assert_eq!(
+            colour.swatch(ColourMode::TrueColour),
+            "\u{1b}[38;2;224;160;176m██\u{1b}[0m"
+        );
+        assert_eq!(
+            colour.swatch(ColourMode::Ansi256),
+            "\u{1b}[38;5;181m██\u{1b}[0m"
+        );
+        assert_eq!(colour.swatch(ColourMode::Plain), "");
+        assert_eq!(colour.to_hex(), "#e0a0b0");
+    }
+
+    /// Tests the colour mode detected from the environment.
+    #[test]
+    fn colour_mode_from_env() {
+        assert_eq!(
+            ColourMode::from_env(true, Some("truecolor")),
+            ColourMode::Plain
+        );
+        assert_eq!(
+            ColourMode::from_env(false, Some("truecolor")),
+            ColourMode::TrueColour
+        );
+        assert_eq!(
+            ColourMode::from_env(false, Some("24bit")),
+            ColourMode::TrueColour
+        );
+        assert_eq!(ColourMode::from_env(false, None), ColourMode::Ansi256);
+    }
+}
@@ -1,5 +1,7 @@
 //! Module for common data that can be modified.
 
+use crate::brands;
+
 /// The maximum number of results to retrieve.
 /// Used for avoid to retrieve too many results.
 pub const MAX_RESULTS: usize = 200;
@@ -11,6 +13,10 @@ pub struct Configuration {
     min_similarity: f32,
     /// The max numbers of results.
     max_results: usize,
+    /// The brands of the products to retrieve, empty for every brand.
+    brands: Vec<String>,
+    /// The brands of the products to skip.
+    excluded_brands: Vec<String>,
 }
 
 impl Configuration {
@@ -23,8 +29,20 @@ impl Configuration {
         Self {
             min_similarity,
             max_results,
+            brands: Vec::new(),
+            excluded_brands: Vec::new(),
         }
     }
+    /// Returns the configuration retrieving only the products of some brands.
+    ///
+    /// # Arguments
+    /// brands - The brands of the products to retrieve, empty for every brand.
+    /// excluded_brands - The brands of the products to skip.
+    pub fn with_brands(mut self, brands: Vec<String>, excluded_brands: Vec<String>) -> Self {
+        self.brands = brands;
+        self.excluded_brands = excluded_brands;
+        self
+    }
     /// Returns the minimum similarity value.
     pub fn min_similarity(&self) -> f32 {
         self.min_similarity
@@ -33,6 +51,11 @@ impl Configuration {
     pub fn max_results(&self) -> usize {
         self.max_results
     }
+    /// Returns true if the products of the brand have to be retrieved.
+    /// Used to skip the product pages of other brands in the search results.
+    pub fn accepts_brand(&self, brand: Option<&str>) -> bool {
+        brands::is_accepted(brand, &self.brands, &self.excluded_brands)
+    }
 }
 
 #[cfg(test)]
@@ -44,5 +67,15 @@ mod tests {
         let configuration = Configuration::new(0.1, 10);
         assert_eq!(configuration.min_similarity(), 0.1);
         assert_eq!(configuration.max_results(), 10);
+        assert!(configuration.accepts_brand(None));
+    }
+
+    #[test]
+    fn configuration_with_brands() {
+        let configuration =
+            Configuration::new(0.1, 10).with_brands(vec![String::from("Milani")], Vec::new());
+        assert!(configuration.accepts_brand(Some("MILANI")));
+        assert!(!configuration.accepts_brand(Some("NYX")));
+        assert!(!configuration.accepts_brand(None));
     }
 }
@@ -163,6 +163,27 @@ pub mod scrapping {
     }
 }
 
+/// Requests to the websites, sharing the connections between the products and the searches of the process.
+pub mod http {
+    use std::sync::OnceLock;
+
+    use reqwest::blocking::{Client, Response};
+
+    /// Client of every request, it keeps the connections open to be reused.
+    static CLIENT: OnceLock<Client> = OnceLock::new();
+
+    /// Sends a GET request with the shared client.
+    ///
+    /// # Arguments
+    /// url - The url to request.
+    ///
+    /// # Returns
+    /// The response, or an error if the request could not be sent.
+    pub fn get(url: &str) -> reqwest::Result<Response> {
+        CLIENT.get_or_init(Client::new).get(url).send()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use scraper::Html;
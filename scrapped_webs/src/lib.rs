@@ -1,6 +1,11 @@
+pub mod brands;
+pub mod colour;
 pub mod product;
 pub mod scrappable;
 pub mod webs;
 
 pub mod configuration;
 mod helper;
+pub mod locale;
+pub mod similarity;
+pub mod synonyms;
@@ -0,0 +1,240 @@
+//! Languages and number formats of the output.
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// Language and number format of the output.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Locale {
+    /// British English, "€9.90".
+    #[default]
+    EnGb,
+    /// Spanish from Spain, "9,90 €".
+    EsEs,
+}
+
+/// Texts shown in the output that depend on the locale.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Label {
+    Available,
+    SoldOut,
+    Was,
+    Now,
+    Rating,
+    Yes,
+    No,
+    Product,
+    Best,
+    NoProductsFound,
+    Summary,
+    Overall,
+    Found,
+    FilteredBySimilarity,
+    Shown,
+    To,
+    Average,
+    OnSale,
+    UnavailableTones,
+    UnavailableProducts,
+    Prices,
+    From,
+    ResultsFor,
+    SortBy,
+    Website,
+    Cheapest,
+    MostExpensive,
+    AveragePrice,
+    AverageDiscount,
+    Time,
+    Similarity,
+    Tone,
+    Price,
+    Availability,
+}
+
+impl Locale {
+    /// Returns the locale of the environment, based on the LC_ALL, LC_MESSAGES and LANG variables.
+    pub fn detect() -> Self {
+        let language = ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|variable| std::env::var(variable).ok())
+            .find(|value| !value.is_empty());
+        Self::from_env(language.as_deref())
+    }
+
+    /// Returns the locale for the value of a locale variable, e.g. "es_ES.UTF-8".
+    /// English is used for any language without translation.
+    fn from_env(language: Option<&str>) -> Self {
+        match language {
+            Some(language) if language.to_lowercase().starts_with("es") => Locale::EsEs,
+            _ => Locale::EnGb,
+        }
+    }
+
+    /// Returns the text of a label in this locale.
+    pub fn label(&self, label: Label) -> &'static str {
+        match (self, label) {
+            (Locale::EnGb, Label::Available) => "available",
+            (Locale::EnGb, Label::SoldOut) => "sold out",
+            (Locale::EnGb, Label::Was) => "was",
+            (Locale::EnGb, Label::Now) => "now",
+            (Locale::EnGb, Label::Rating) => "rating",
+            (Locale::EnGb, Label::Yes) => "yes",
+            (Locale::EnGb, Label::No) => "no",
+            (Locale::EnGb, Label::Product) => "Product",
+            (Locale::EnGb, Label::Best) => "best",
+            (Locale::EnGb, Label::NoProductsFound) => "No products found.",
+            (Locale::EnGb, Label::Summary) => "Summary",
+            (Locale::EnGb, Label::Overall) => "Overall",
+            (Locale::EnGb, Label::Found) => "found",
+            (Locale::EnGb, Label::FilteredBySimilarity) => "filtered by similarity",
+            (Locale::EnGb, Label::Shown) => "shown",
+            (Locale::EnGb, Label::To) => "to",
+            (Locale::EnGb, Label::Average) => "average",
+            (Locale::EnGb, Label::OnSale) => "on sale",
+            (Locale::EnGb, Label::UnavailableTones) => "unavailable tones",
+            (Locale::EnGb, Label::UnavailableProducts) => "unavailable products",
+            (Locale::EnGb, Label::Prices) => "prices",
+            (Locale::EnGb, Label::From) => "from",
+            (Locale::EnGb, Label::ResultsFor) => "Results for",
+            (Locale::EnGb, Label::SortBy) => "Sort by",
+            (Locale::EnGb, Label::Website) => "Website",
+            (Locale::EnGb, Label::Cheapest) => "Cheapest",
+            (Locale::EnGb, Label::MostExpensive) => "Most expensive",
+            (Locale::EnGb, Label::AveragePrice) => "Average price",
+            (Locale::EnGb, Label::AverageDiscount) => "Average discount",
+            (Locale::EnGb, Label::Time) => "Time",
+            (Locale::EnGb, Label::Similarity) => "Similarity",
+            (Locale::EnGb, Label::Tone) => "Tone",
+            (Locale::EnGb, Label::Price) => "Price",
+            (Locale::EnGb, Label::Availability) => "Availability",
+            (Locale::EsEs, Label::Available) => "disponible",
+            (Locale::EsEs, Label::SoldOut) => "agotado",
+            (Locale::EsEs, Label::Was) => "antes",
+            (Locale::EsEs, Label::Now) => "ahora",
+            (Locale::EsEs, Label::Rating) => "valoración",
+            (Locale::EsEs, Label::Yes) => "sí",
+            (Locale::EsEs, Label::No) => "no",
+            (Locale::EsEs, Label::Product) => "Producto",
+            (Locale::EsEs, Label::Best) => "mejor",
+            (Locale::EsEs, Label::NoProductsFound) => "No se han encontrado productos.",
+            (Locale::EsEs, Label::Summary) => "Resumen",
+            (Locale::EsEs, Label::Overall) => "Total",
+            (Locale::EsEs, Label::Found) => "encontrados",
+            (Locale::EsEs, Label::FilteredBySimilarity) => "filtrados por similitud",
+            (Locale::EsEs, Label::Shown) => "mostrados",
+            (Locale::EsEs, Label::To) => "a",
+            (Locale::EsEs, Label::Average) => "media",
+            (Locale::EsEs, Label::OnSale) => "en oferta",
+            (Locale::EsEs, Label::UnavailableTones) => "tonos agotados",
+            (Locale::EsEs, Label::UnavailableProducts) => "productos agotados",
+            (Locale::EsEs, Label::Prices) => "precios",
+            (Locale::EsEs, Label::From) => "desde",
+            (Locale::EsEs, Label::ResultsFor) => "Resultados de",
+            (Locale::EsEs, Label::SortBy) => "Ordenar por",
+            (Locale::EsEs, Label::Website) => "Web",
+            (Locale::EsEs, Label::Cheapest) => "Más barato",
+            (Locale::EsEs, Label::MostExpensive) => "Más caro",
+            (Locale::EsEs, Label::AveragePrice) => "Precio medio",
+            (Locale::EsEs, Label::AverageDiscount) => "Descuento medio",
+            (Locale::EsEs, Label::Time) => "Tiempo",
+            (Locale::EsEs, Label::Similarity) => "Similitud",
+            (Locale::EsEs, Label::Tone) => "Tono",
+            (Locale::EsEs, Label::Price) => "Precio",
+            (Locale::EsEs, Label::Availability) => "Disponibilidad",
+        }
+    }
+
+    /// Formats a number with the decimal separator of the locale.
+    /// # Arguments
+    /// value - The number to format.
+    /// decimals - The number of decimals, always printed.
+    /// # Example
+    /// assert_eq!(Locale::EsEs.number(9.5, 2), "9,50");
+    pub fn number(&self, value: f32, decimals: usize) -> String {
+        let number = format!("{value:.decimals$}");
+        match self {
+            Locale::EnGb => number,
+            Locale::EsEs => number.replace('.', ","),
+        }
+    }
+
+    /// Formats a price in euros with two decimals and the symbol where the locale places it.
+    /// # Example
+    /// en-GB = €9.90
+    /// es-ES = 9,90 €
+    pub fn price(&self, price: f32) -> String {
+        match self {
+            Locale::EnGb => format!("€{}", self.number(price, 2)),
+            Locale::EsEs => format!("{} €", self.number(price, 2)),
+        }
+    }
+
+    /// Formats a percentage with two decimals.
+    /// # Example
+    /// en-GB = 95.42%
+    /// es-ES = 95,42 %
+    pub fn percentage(&self, value: f32) -> String {
+        match self {
+            Locale::EnGb => format!("{}%", self.number(value, 2)),
+            Locale::EsEs => format!("{} %", self.number(value, 2)),
+        }
+    }
+}
+
+impl FromStr for Locale {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text.to_lowercase().replace('_', "-").as_str() {
+            "en-gb" => Ok(Locale::EnGb),
+            "es-es" => Ok(Locale::EsEs),
+            _ => Err(format!("unsupported locale {text}, use es-ES or en-GB")),
+        }
+    }
+}
+
+impl Display for Locale {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Locale::EnGb => write!(f, "en-GB"),
+            Locale::EsEs => write!(f, "es-ES"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests the numbers, prices and percentages of every locale.
+    #[test]
+    fn number_formats() {
+        assert_eq!(Locale::EnGb.number(9.5, 1), "9.5");
+        assert_eq!(Locale::EsEs.number(9.5, 2), "9,50");
+        assert_eq!(Locale::EnGb.price(9.9), "€9.90");
+        assert_eq!(Locale::EsEs.price(9.9), "9,90 €");
+        assert_eq!(Locale::EnGb.price(10.0), "€10.00");
+        assert_eq!(Locale::EnGb.percentage(95.421), "95.42%");
+        assert_eq!(Locale::EsEs.percentage(95.421), "95,42 %");
+    }
+
+    /// Tests the locale detected from the environment.
+    #[test]
+    fn locale_from_env() {
+        assert_eq!(Locale::from_env(Some("es_ES.UTF-8")), Locale::EsEs);
+        assert_eq!(Locale::from_env(Some("en_GB.UTF-8")), Locale::EnGb);
+        assert_eq!(Locale::from_env(Some("fr_FR.UTF-8")), Locale::EnGb);
+        assert_eq!(Locale::from_env(None), Locale::EnGb);
+    }
+
+    /// Tests the locales accepted from the command line.
+    #[test]
+    fn from_str_all_cases() {
+        assert_eq!("es-ES".parse::<Locale>(), Ok(Locale::EsEs));
+        assert_eq!("en_gb".parse::<Locale>(), Ok(Locale::EnGb));
+        assert!("fr-FR".parse::<Locale>().is_err());
+        assert_eq!(Locale::EsEs.to_string(), "es-ES");
+        assert_eq!(Locale::EsEs.label(Label::SoldOut), "agotado");
+    }
+}
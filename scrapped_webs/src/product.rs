@@ -1,12 +1,19 @@
 //! Product struct declaration and implementations.
 
 use ansi_term;
+use serde::Serialize;
 use std::fmt::Display;
 
+use crate::colour::{ColourMode, Rgb};
 use crate::helper::utilities;
+use crate::locale::{Label, Locale};
+use crate::similarity::SimilarityExplanation;
+
+/// Text shown instead of a field the website did not provide.
+pub const MISSING_FIELD: &str = "N/A";
 
 /// Defines a tone.
-#[derive(Clone, PartialEq, PartialOrd, Debug, Default)]
+#[derive(Clone, PartialEq, PartialOrd, Debug, Default, Serialize)]
 pub struct Tone {
     /// Name of the tone.
     pub name: Option<String>,
@@ -20,6 +27,9 @@ pub struct Tone {
     pub url: Option<String>,
     /// Possible rating.
     pub rating: Option<f32>,
+    /// Colour of the swatch in the website, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub colour: Option<Rgb>,
 }
 
 impl Tone {
@@ -38,61 +48,74 @@ impl Tone {
             available,
             url,
             rating,
+            colour: None,
         }
     }
 
     /// Formats the Tone object to be pretty printed in terminal.
+    /// # Arguments
+    /// colour_mode: How to paint the swatch of the tone, if it has a colour.
+    /// In plain mode only ASCII markers are used, with the same width, and the euro sign of the prices.
+    /// locale: The language of the labels and the format of the prices.
     /// # Example
-    /// if available, on sale and rating = ✔️ Name -  ̶9̶.̶9̶9̶  4.99(50%) - 9.5⭐
-    /// if not available, not on sale and no rating = ❌ Name - 9.99
-    pub fn terminal_format(&self) -> String {
+    /// if available, on sale and rating = ✔️ Name -  ̶€̶9̶.̶9̶9̶ €4.99(50%) - 9.5⭐
+    /// if not available, not on sale and no rating = ❌ Name - €9.99
+    /// if it has a colour = ✔️ ██ Name - €9.99
+    /// plain = [available] Name - was €9.99 now €4.99 (-50%) - rating 9.5
+    /// es-ES = ✔️ Name - 9,99 €
+    pub fn terminal_format(&self, colour_mode: ColourMode, locale: Locale) -> String {
         let mut out: String = String::from("    - ");
-        match self.available {
-            true => out.push_str("✔️   "),
-            false => out.push_str("❌   "),
+        match (colour_mode, self.available) {
+            (ColourMode::Plain, available) => {
+                let available_marker = format!("[{}]", locale.label(Label::Available));
+                let sold_out_marker = format!("[{}]", locale.label(Label::SoldOut));
+                let width = available_marker
+                    .chars()
+                    .count()
+                    .max(sold_out_marker.chars().count());
+                let marker = if available {
+                    available_marker
+                } else {
+                    sold_out_marker
+                };
+                out.push_str(format!("{marker:<width$} ").as_str());
+            }
+            (_, true) => out.push_str("✔️   "),
+            (_, false) => out.push_str("❌   "),
         }
-        out.push_str(format!("{} - ", self.name.as_ref().unwrap()).as_str());
-        match self.price_sales {
-            Some(price_sales) => {
-                let strikedthrought_price = ansi_term::Style::new()
-                    .strikethrough()
-                    .paint(self.price_standard.unwrap().to_string())
-                    .to_string();
-                out.push_str(
-                    format!(
-                        "{}€ {}€({}%)",
-                        strikedthrought_price,
-                        price_sales,
-                        utilities::discount(self.price_standard.unwrap(), self.price_sales)
-                            .unwrap()
-                            .1
-                    )
-                    .as_str(),
-                )
+        if let Some(colour) = self.colour {
+            if colour_mode != ColourMode::Plain {
+                out.push_str(format!("{} ", colour.swatch(colour_mode)).as_str());
             }
-            None => out.push_str(format!("{}€", self.price_standard.unwrap()).as_str()),
         }
+        out.push_str(format!("{} - ", self.name.as_deref().unwrap_or(MISSING_FIELD)).as_str());
+        out.push_str(
+            price_terminal_format(self.price_standard, self.price_sales, colour_mode, locale)
+                .as_str(),
+        );
         if let Some(rating) = self.rating {
-            out.push_str(format!(" - {rating}⭐").as_str())
+            out.push_str(rating_terminal_format(rating, colour_mode, locale).as_str())
         }
         out
     }
 
-    /// Returns the actual price, doesn't matter if on sale or not
-    pub fn price(&self) -> f32 {
-        if self.price_sales.is_some() {
-            self.price_sales.unwrap()
-        } else {
-            self.price_standard.unwrap()
-        }
+    /// Returns the percentage of discount if the tone is on sale.
+    pub fn discount_percentage(&self) -> Option<u8> {
+        utilities::discount(self.price_standard?, self.price_sales)
+            .map(|(_, percentage)| percentage)
+    }
+
+    /// Returns the actual price, doesn't matter if on sale or not, None if the website has no price.
+    pub fn price(&self) -> Option<f32> {
+        self.price_sales.or(self.price_standard)
     }
 }
 
 impl Display for Tone {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut out: String = String::new();
-        out.push_str(format!("Name: {}", self.name.as_ref().unwrap()).as_str());
-        out.push_str(format!("\nPrice: {}", self.price_standard.unwrap()).as_str());
+        out.push_str(format!("Name: {}", self.name.as_deref().unwrap_or(MISSING_FIELD)).as_str());
+        out.push_str(format!("\nPrice: {}", optional_format(self.price_standard)).as_str());
         if let Some(price_sales) = self.price_sales {
             out.push_str(format!("\nPrice on sale: {price_sales}").as_str());
         }
@@ -102,7 +125,7 @@ impl Display for Tone {
 }
 
 /// Defines a product we can obtain web scraping the website
-#[derive(Clone, PartialEq, PartialOrd, Debug, Default)]
+#[derive(Clone, PartialEq, PartialOrd, Debug, Default, Serialize)]
 pub struct Product {
     /// The product name.
     pub name: String,
@@ -118,6 +141,9 @@ pub struct Product {
     pub rating: Option<f32>,
     /// Similarity between the product name to search and the one found.
     pub similarity: f32,
+    /// Breakdown of how the similarity was computed, only if requested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub similarity_explanation: Option<SimilarityExplanation>,
     /// Available of the product.
     pub available: bool,
     /// The list of tones for this product.
@@ -157,69 +183,103 @@ impl Product {
             tones,
             rating,
             similarity,
+            similarity_explanation: None,
             available,
         }
     }
 
     /// Formats the Tone object to be pretty printed in terminal.
     /// # Example
-    ///  if has tones: 95%. Labial Rare Beauty - 10.99-15.99 - 9.5⭐: www.test.com
-    ///  if doesn't have tones: 95% - Labial Rare Beauty - 10.99 - 9.5⭐: www.test.com
-    pub fn terminal_format(&self) -> String {
+    ///  if has tones: 95.00%. Labial Rare Beauty - €10.99 - €15.99 - 9.5⭐: www.test.com
+    ///  if doesn't have tones: 95.00% - Labial Rare Beauty - €10.99 - 9.5⭐: www.test.com
+    ///  plain: 95.00% - Labial Rare Beauty - was €10.99 now €8.99 (-18%) - rating 9.5: www.test.com
+    ///  es-ES: 95,00 % - Labial Rare Beauty - 10,99 € - 9,5⭐: www.test.com
+    pub fn terminal_format(&self, colour_mode: ColourMode, locale: Locale) -> String {
         let mut out: String = String::new();
-        out.push_str(format!("- {}. ", self.similarity_formatted()).as_str());
-        out.push_str(format!("{} - {} - ", self.name, self.brand.as_ref().unwrap()).as_str());
+        out.push_str(format!("- {}. ", self.similarity_formatted(locale)).as_str());
+        out.push_str(
+            format!(
+                "{} - {} - ",
+                self.name,
+                self.brand.as_deref().unwrap_or(MISSING_FIELD)
+            )
+            .as_str(),
+        );
 
         match self.tones.as_ref() {
             // If we have tones, look for the lowest and highest price
             Some(tones) => {
-                let mut lowest_price: f32 = f32::MAX;
-                let mut highest_price: f32 = f32::MIN;
-                for tone in tones {
-                    let price = tone.price();
-                    if price > highest_price {
-                        highest_price = price;
-                    }
-                    if price < lowest_price {
-                        lowest_price = price;
-                    }
-                }
-                out.push_str(format!("{lowest_price}€-{highest_price}€").as_str());
-            }
-            None => match self.price_sales {
-                Some(price_sales) => {
-                    let strikedthrought_price = ansi_term::Style::new()
-                        .strikethrough()
-                        .paint(self.price_standard.unwrap().to_string())
-                        .to_string();
-                    out.push_str(
+                let prices: Vec<f32> = tones.iter().filter_map(Tone::price).collect();
+                let lowest_price = prices.iter().copied().reduce(f32::min);
+                let highest_price = prices.iter().copied().reduce(f32::max);
+                match (lowest_price, highest_price) {
+                    (Some(lowest_price), Some(highest_price)) => out.push_str(
                         format!(
-                            "{}€ {}€({}%)",
-                            strikedthrought_price,
-                            price_sales,
-                            utilities::discount(self.price_standard.unwrap(), self.price_sales)
-                                .unwrap()
-                                .1
+                            "{} - {}",
+                            locale.price(lowest_price),
+                            locale.price(highest_price)
                         )
                         .as_str(),
-                    )
+                    ),
+                    _ => out.push_str(MISSING_FIELD),
                 }
-                None => out.push_str(format!("{}€", self.price_standard.unwrap()).as_str()),
-            },
+            }
+            None => out.push_str(
+                price_terminal_format(self.price_standard, self.price_sales, colour_mode, locale)
+                    .as_str(),
+            ),
         }
 
         if let Some(rating) = self.rating {
-            out.push_str(format!(" - {rating}⭐").as_str());
+            out.push_str(rating_terminal_format(rating, colour_mode, locale).as_str());
         }
         out.push_str(format!(": {}", self.link).as_str());
         out
     }
 
+    /// Returns the percentage of discount if the product is on sale.
+    pub fn discount_percentage(&self) -> Option<u8> {
+        utilities::discount(self.price_standard?, self.price_sales)
+            .map(|(_, percentage)| percentage)
+    }
+
+    /// Returns the actual price of the product itself, doesn't matter if on sale or not, None if the website has no price.
+    pub fn price(&self) -> Option<f32> {
+        self.price_sales.or(self.price_standard)
+    }
+
+    /// Returns the price to pay for the product: the actual price of its cheapest tone if it has tones,
+    /// its own actual price otherwise. None if the website has no price.
+    pub fn effective_price(&self) -> Option<f32> {
+        match self.tones.as_ref() {
+            Some(tones) => tones.iter().filter_map(Tone::price).reduce(f32::min),
+            None => self.price(),
+        }
+    }
+
+    /// Returns the rating of the product: its own rating, or the best rating of its tones
+    /// if it has tones and no rating of its own (e.g. in Maquillalia). None if there is no rating.
+    pub fn effective_rating(&self) -> Option<f32> {
+        self.rating.or_else(|| {
+            self.tones
+                .as_ref()
+                .and_then(|tones| tones.iter().filter_map(|tone| tone.rating).reduce(f32::max))
+        })
+    }
+
     /// Returns the similarity rounded and formatted
     /// # Example
-    /// .621242 = 62.12%
-    fn similarity_formatted(&self) -> String {
-        format!("{:.2}%", self.similarity * 100.0)
+    /// .621242 = 62.12% (en-GB) or 62,12 % (es-ES)
+    fn similarity_formatted(&self, locale: Locale) -> String {
+        locale.percentage(self.similarity * 100.0)
+    }
+
+    /// Returns the brand and the name, or only the name if the brand is unknown.
+    pub fn full_name(&self) -> String {
+        match self.brand.as_ref() {
+            Some(brand) => format!("{} {}", brand, self.name),
+            None => self.name.clone(),
+        }
     }
 
     /// Adds a new Tone.
@@ -235,9 +295,15 @@ impl Display for Product {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut out: String = String::new();
         out.push_str(format!("Name: {}", self.name).as_str());
-        out.push_str(format!("\nBrand: {}", self.brand.as_ref().unwrap()).as_str());
+        out.push_str(
+            format!(
+                "\nBrand: {}",
+                self.brand.as_deref().unwrap_or(MISSING_FIELD)
+            )
+            .as_str(),
+        );
         out.push_str(format!("\nLink: {}", self.link).as_str());
-        out.push_str(format!("\nPrice: {}", self.price_standard.unwrap()).as_str());
+        out.push_str(format!("\nPrice: {}", optional_format(self.price_standard)).as_str());
         if let Some(price_sales) = self.price_sales {
             out.push_str(format!("\nPrice on sale: {price_sales}").as_str());
         }
@@ -254,7 +320,66 @@ impl Display for Product {
     }
 }
 
+/// Formats a price to be printed in terminal, struck through with the discount if it is on sale.
+/// The prices always have two decimals and the currency symbol where the locale places it.
+/// # Example
+/// on sale =  ̶€̶9̶.̶9̶9̶ €4.99(50%)
+/// on sale in plain mode = was €9.99 now €4.99 (-50%)
+/// not on sale = €9.99 (en-GB) or 9,99 € (es-ES)
+/// without price = N/A
+fn price_terminal_format(
+    price_standard: Option<f32>,
+    price_sales: Option<f32>,
+    colour_mode: ColourMode,
+    locale: Locale,
+) -> String {
+    match (price_standard, price_sales) {
+        (Some(price_standard), Some(price_sales)) => {
+            match utilities::discount(price_standard, Some(price_sales)) {
+                Some((_, percentage)) if colour_mode == ColourMode::Plain => format!(
+                    "{} {} {} {} (-{percentage}%)",
+                    locale.label(Label::Was),
+                    locale.price(price_standard),
+                    locale.label(Label::Now),
+                    locale.price(price_sales)
+                ),
+                Some((_, percentage)) => {
+                    let strikedthrought_price = ansi_term::Style::new()
+                        .strikethrough()
+                        .paint(locale.price(price_standard))
+                        .to_string();
+                    format!(
+                        "{strikedthrought_price} {}({percentage}%)",
+                        locale.price(price_sales)
+                    )
+                }
+                None => locale.price(price_sales),
+            }
+        }
+        (None, Some(price)) | (Some(price), None) => locale.price(price),
+        (None, None) => String::from(MISSING_FIELD),
+    }
+}
+
+/// Formats a rating to be printed in terminal, with a star or as text in plain mode.
+fn rating_terminal_format(rating: f32, colour_mode: ColourMode, locale: Locale) -> String {
+    match colour_mode {
+        ColourMode::Plain => format!(
+            " - {} {}",
+            locale.label(Label::Rating),
+            locale.number(rating, 1)
+        ),
+        _ => format!(" - {}⭐", locale.number(rating, 1)),
+    }
+}
+
+/// Formats an optional value, with a placeholder if it is missing.
+fn optional_format<T: Display>(value: Option<T>) -> String {
+    value.map_or(String::from(MISSING_FIELD), |value| value.to_string())
+}
+
 #[cfg(test)]
+#[allow(clippy::unnecessary_literal_unwrap)]
 mod tests {
     use super::*;
 
@@ -273,7 +398,7 @@ mod tests {
             price_sales,
             available,
             url.clone(),
-            rating.clone(),
+            rating,
         );
 
         // Getters
@@ -294,10 +419,10 @@ mod tests {
 
         tone.name = Some(set_name.clone());
         tone.price_standard = Some(set_price_standard);
-        tone.price_sales = set_price_sales.clone();
+        tone.price_sales = set_price_sales;
         tone.available = set_available;
         tone.url = set_url.clone();
-        tone.rating = set_rating.clone();
+        tone.rating = set_rating;
 
         println!("Testing Debug trait implementation for Tone: {:?}", tone);
         println!("Testing Display trait implementation for Tone: {}", tone);
@@ -321,7 +446,7 @@ mod tests {
             price_sales,
             available,
             url.clone(),
-            tone_rating.clone(),
+            tone_rating,
         )]);
         let rating: Option<f32> = Some(4.5);
         let similarity: f32 = 0.86;
@@ -418,7 +543,7 @@ mod tests {
             set_price_sales,
             set_available,
             set_url.clone(),
-            set_tone_rating.clone(),
+            set_tone_rating,
         )]);
         let set_rating: Option<f32> = Some(4.0);
         let set_similarity: f32 = 0.75;
@@ -427,9 +552,9 @@ mod tests {
         product.brand = set_brand.clone();
         product.link = set_link.clone();
         product.price_standard = set_price_standard;
-        product.price_sales = set_price_sales.clone();
+        product.price_sales = set_price_sales;
         product.tones = set_tones.clone();
-        product.rating = set_rating.clone();
+        product.rating = set_rating;
         product.similarity = set_similarity;
         product.available = set_available;
 
@@ -452,12 +577,13 @@ mod tests {
         let tone_on_sale: Tone = Tone {
             name: Some(String::from("Tone 1")),
             price_standard,
-            price_sales: price_sales,
+            price_sales,
             available: true,
             url: None,
             rating: Some(9.5),
+            colour: None,
         };
-        assert_eq!(tone_on_sale.price(), price_sales.unwrap());
+        assert_eq!(tone_on_sale.price(), price_sales);
 
         // Tone without price on sale.
         let tone: Tone = Tone {
@@ -467,8 +593,64 @@ mod tests {
             available: true,
             url: None,
             rating: Some(9.5),
+            colour: None,
         };
-        assert_eq!(tone.price(), price_standard.unwrap());
+        assert_eq!(tone.price(), price_standard);
+    }
+
+    /// Tests the discount_percentage function for tones and products.
+    #[test]
+    fn discount_percentage_all_paths() {
+        let mut tone: Tone = Tone::new(None, Some(10.0), Some(7.5), true, None, None);
+        assert_eq!(tone.discount_percentage(), Some(25));
+        tone.price_sales = None;
+        assert_eq!(tone.discount_percentage(), None);
+        tone.price_standard = None;
+        tone.price_sales = Some(7.5);
+        assert_eq!(tone.discount_percentage(), None);
+
+        let mut product: Product = Product::default();
+        assert_eq!(product.discount_percentage(), None);
+        product.price_standard = Some(20.0);
+        product.price_sales = Some(10.0);
+        assert_eq!(product.discount_percentage(), Some(50));
+    }
+
+    /// Tests the effective price of products with and without tones.
+    #[test]
+    fn effective_price_all_paths() {
+        let mut product: Product = Product::default();
+        assert_eq!(product.effective_price(), None);
+        product.price_standard = Some(20.0);
+        assert_eq!(product.effective_price(), Some(20.0));
+        product.price_sales = Some(15.0);
+        assert_eq!(product.price(), Some(15.0));
+        assert_eq!(product.effective_price(), Some(15.0));
+
+        // With tones the price of the product itself is not used.
+        product.tones = Some(vec![
+            Tone::new(None, Some(30.0), Some(12.0), true, None, None),
+            Tone::new(None, Some(10.0), None, false, None, None),
+            Tone::new(None, None, None, true, None, None),
+        ]);
+        assert_eq!(product.effective_price(), Some(10.0));
+        product.tones = Some(vec![Tone::default()]);
+        assert_eq!(product.effective_price(), None);
+    }
+
+    /// Tests the rating of products rated by themselves and only through their tones.
+    #[test]
+    fn effective_rating_all_paths() {
+        let mut product: Product = Product::default();
+        assert_eq!(product.effective_rating(), None);
+        product.tones = Some(vec![
+            Tone::new(None, None, None, true, None, Some(7.5)),
+            Tone::new(None, None, None, true, None, Some(9.0)),
+            Tone::new(None, None, None, true, None, None),
+        ]);
+        assert_eq!(product.effective_rating(), Some(9.0));
+        product.rating = Some(8.0);
+        assert_eq!(product.effective_rating(), Some(8.0));
     }
 
     /// Tests the function Product::terminal_format without tones and on sale.
@@ -482,10 +664,11 @@ mod tests {
             price_sales: None,
             rating: Some(9.5),
             similarity: 0.9,
+            similarity_explanation: None,
             available: true,
             tones: None,
         };
-        product.terminal_format();
+        product.terminal_format(ColourMode::Ansi256, Locale::EnGb);
 
         let product_on_sale: Product = Product {
             name: String::from("Product 1"),
@@ -495,10 +678,11 @@ mod tests {
             price_sales: Some(5.0),
             rating: Some(9.5),
             similarity: 0.9,
+            similarity_explanation: None,
             available: true,
             tones: None,
         };
-        product_on_sale.terminal_format();
+        product_on_sale.terminal_format(ColourMode::Ansi256, Locale::EnGb);
         // assert_eq!(product.terminal_format(), "90%. Product 1 Brand - 10€ 5€(50%) - 9.5⭐: http://www.test.com");
     }
 
@@ -512,6 +696,7 @@ mod tests {
             available: true,
             url: None,
             rating: None,
+            colour: None,
         };
         let tone_on_sale: Tone = Tone {
             name: Some(String::from("Tone 1")),
@@ -520,6 +705,7 @@ mod tests {
             available: true,
             url: None,
             rating: None,
+            colour: None,
         };
 
         let product: Product = Product {
@@ -530,12 +716,13 @@ mod tests {
             price_sales: Some(5.0),
             rating: Some(9.5),
             similarity: 0.95421,
+            similarity_explanation: None,
             available: true,
             tones: Some(vec![tone, tone_on_sale]),
         };
         assert_eq!(
-            product.terminal_format(),
-            "- 95.42%. Product 1 - Brand - 5€-50.99€ - 9.5⭐: http://www.test.com"
+            product.terminal_format(ColourMode::Ansi256, Locale::EnGb),
+            "- 95.42%. Product 1 - Brand - €5.00 - €50.99 - 9.5⭐: http://www.test.com"
         );
     }
 
@@ -549,8 +736,9 @@ mod tests {
             available: true,
             url: None,
             rating: Some(9.5),
+            colour: None,
         };
-        tone.terminal_format();
+        tone.terminal_format(ColourMode::Ansi256, Locale::EnGb);
         // assert_eq!(output, "✔️   Tone 1 -  ̶10€ 5€(50%) - 9.5⭐"); Can not test strikethrough text
     }
 
@@ -564,8 +752,12 @@ mod tests {
             available: false,
             url: None,
             rating: None,
+            colour: None,
         };
-        assert_eq!(tone.terminal_format(), "    - ❌   Tone 1 - 10€");
+        assert_eq!(
+            tone.terminal_format(ColourMode::Ansi256, Locale::EnGb),
+            "    - ❌   Tone 1 - €10.00"
+        );
     }
 
     /// Tests the function Tone::terminal_format with a tone unavailable and with rating
@@ -578,8 +770,12 @@ mod tests {
             available: false,
             url: None,
             rating: Some(9.5),
+            colour: None,
         };
-        assert_eq!(tone.terminal_format(), "    - ❌   Tone 1 - 10€ - 9.5⭐");
+        assert_eq!(
+            tone.terminal_format(ColourMode::Ansi256, Locale::EnGb),
+            "    - ❌   Tone 1 - €10.00 - 9.5⭐"
+        );
     }
 
     /// Tests the function Tone::terminal_format with a tone unavailable, on sale and without rating
@@ -592,8 +788,137 @@ mod tests {
             available: false,
             url: None,
             rating: None,
+            colour: None,
         };
-        tone.terminal_format();
+        tone.terminal_format(ColourMode::Ansi256, Locale::EnGb);
         // assert_eq!(output, "❌   Tone 1 -  ̶10€ 5€(50%)"); Can not test strikethrough text
     }
+
+    /// Tests the function Tone::terminal_format paints the swatch only if there are colours.
+    #[test]
+    fn tone_format_terminal_with_colour() {
+        let mut tone: Tone = Tone::new(
+            Some(String::from("Tone 1")),
+            Some(10.0),
+            None,
+            true,
+            None,
+            None,
+        );
+        tone.colour = Some(Rgb::new(224, 160, 176));
+        assert_eq!(
+            tone.terminal_format(ColourMode::TrueColour, Locale::EnGb),
+            "    - ✔️   \u{1b}[38;2;224;160;176m██\u{1b}[0m Tone 1 - €10.00"
+        );
+        assert_eq!(
+            tone.terminal_format(ColourMode::Ansi256, Locale::EnGb),
+            "    - ✔️   \u{1b}[38;5;181m██\u{1b}[0m Tone 1 - €10.00"
+        );
+        assert_eq!(
+            tone.terminal_format(ColourMode::Plain, Locale::EnGb),
+            "    - [available] Tone 1 - €10.00"
+        );
+    }
+
+    /// Tests the plain mode only uses ASCII, with markers of the same width and textual sale prices.
+    #[test]
+    fn format_terminal_plain() {
+        let mut tone: Tone = Tone::new(
+            Some(String::from("Tone 1")),
+            Some(10.0),
+            Some(5.0),
+            true,
+            None,
+            Some(9.5),
+        );
+        tone.colour = Some(Rgb::new(224, 160, 176));
+        assert_eq!(
+            tone.terminal_format(ColourMode::Plain, Locale::EnGb),
+            "    - [available] Tone 1 - was €10.00 now €5.00 (-50%) - rating 9.5"
+        );
+        tone.available = false;
+        assert_eq!(
+            tone.terminal_format(ColourMode::Plain, Locale::EnGb),
+            "    - [sold out]  Tone 1 - was €10.00 now €5.00 (-50%) - rating 9.5"
+        );
+
+        let product: Product = Product {
+            name: String::from("Product 1"),
+            brand: Some(String::from("Brand")),
+            link: String::from("http://www.test.com"),
+            price_standard: Some(10.0),
+            price_sales: Some(5.0),
+            rating: Some(9.5),
+            similarity: 0.9,
+            ..Default::default()
+        };
+        let output = product.terminal_format(ColourMode::Plain, Locale::EnGb);
+        assert_eq!(
+            output,
+            "- 90.00%. Product 1 - Brand - was €10.00 now €5.00 (-50%) - rating 9.5: http://www.test.com"
+        );
+        assert!(!output.contains('\u{1b}'));
+    }
+
+    /// Tests the labels, decimal separators and currency symbol of the Spanish locale.
+    #[test]
+    fn format_terminal_es_es() {
+        let tone: Tone = Tone::new(
+            Some(String::from("Tono 1")),
+            Some(9.9),
+            Some(4.95),
+            false,
+            None,
+            Some(9.5),
+        );
+        assert_eq!(
+            tone.terminal_format(ColourMode::Plain, Locale::EsEs),
+            "    - [agotado]    Tono 1 - antes 9,90 € ahora 4,95 € (-50%) - valoración 9,5"
+        );
+
+        let product: Product = Product {
+            name: String::from("Producto 1"),
+            brand: Some(String::from("Marca")),
+            link: String::from("http://www.test.com"),
+            price_standard: Some(12.5),
+            rating: Some(8.0),
+            similarity: 0.95421,
+            tones: None,
+            ..Default::default()
+        };
+        assert_eq!(
+            product.terminal_format(ColourMode::Ansi256, Locale::EsEs),
+            "- 95,42 %. Producto 1 - Marca - 12,50 € - 8,0⭐: http://www.test.com"
+        );
+    }
+
+    /// Tests a product without any of the optional fields is rendered with placeholders.
+    #[test]
+    fn format_all_none_product() {
+        let tone: Tone = Tone::default();
+        assert_eq!(
+            tone.terminal_format(ColourMode::TrueColour, Locale::EnGb),
+            "    - ❌   N/A - N/A"
+        );
+        assert_eq!(tone.to_string(), "Name: N/A\nPrice: N/A\n");
+        assert_eq!(tone.price(), None);
+
+        let product: Product = Product::default();
+        assert_eq!(
+            product.terminal_format(ColourMode::Plain, Locale::EnGb),
+            "- 0.00%.  - N/A - N/A: "
+        );
+        assert!(product.to_string().contains("Brand: N/A\n"));
+        assert!(product.to_string().contains("Price: N/A\n"));
+        assert_eq!(product.full_name(), "");
+
+        let product_with_tones: Product = Product {
+            tones: Some(vec![Tone::default(), Tone::default()]),
+            ..Default::default()
+        };
+        assert_eq!(
+            product_with_tones.terminal_format(ColourMode::TrueColour, Locale::EnGb),
+            "- 0.00%.  - N/A - N/A: "
+        );
+    }
 }
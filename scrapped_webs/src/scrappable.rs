@@ -3,6 +3,7 @@ use anyhow;
 use scraper::{ElementRef, Html};
 use thiserror;
 
+use crate::helper::http;
 use crate::product::{Product, Tone};
 
 /// Enumeration of possible error when trying to search a product.
@@ -16,28 +17,54 @@ pub enum SearchError {
     NotFound,
 }
 
+/// What a search found in a website.
+#[derive(Debug, Default, PartialEq)]
+pub struct SearchResults<T = Product> {
+    /// The results with at least the minimum similarity: their urls in the search page, or the products retrieved.
+    pub similar: Vec<T>,
+    /// The urls of every result of the brands accepted, also of the ones below the minimum similarity.
+    pub found: Vec<String>,
+}
+
 pub trait Scrappable {
+    /// Searches the product in the website.
+    ///
+    /// # Arguments
+    /// name - The name of the product to find.
+    /// # Returns
+    /// Ok - The similar products that match the name and the urls of every result found,
+    /// without products if none of the results is similar enough or of the brands accepted.
+    /// Err - If the search could not be done or there are no results (`SearchError::NotFound`).
+    fn search(&self, name: String) -> Result<SearchResults, anyhow::Error>;
+
     /// Try to find the product in the website.
     ///
     /// # Arguments
     /// name - The name of the product to find.
     /// # Returns
-    /// Product - A vector with the similar products that matches the name.
-    /// Box<dyn Error> - If couldn't find the product.
-    fn look_for_products(&self, name: String) -> Result<Vec<Product>, anyhow::Error>;
+    /// Product - A vector with the similar products that matches the name,
+    /// empty if every result is of a brand not accepted.
+    /// Box<dyn Error> - If couldn't find the product, `SearchError::NotEnoughSimilarity` if no result is similar enough.
+    fn look_for_products(&self, name: String) -> Result<Vec<Product>, anyhow::Error> {
+        let results = self.search(name)?;
+        if results.similar.is_empty() && !results.found.is_empty() {
+            return Err(anyhow::anyhow!(SearchError::NotEnoughSimilarity));
+        }
+        Ok(results.similar)
+    }
 
     /// Returns the url of the products found.
     /// # Arguments
     /// document - The search page HTML document with some or none products found.
     /// name - The name provided by the user to find.
     /// # Returns
-    /// Ok - Vector with the urls found in the search page.
-    /// Err - Search error.
+    /// Ok - The urls of the results with at least the minimum similarity and of every result of the brands accepted.
+    /// Err - `SearchError::NotFound` if the page has no results.
     fn search_results_urls(
         &self,
         document: &Html,
         name: &str,
-    ) -> Result<Vec<String>, anyhow::Error>;
+    ) -> Result<SearchResults<String>, anyhow::Error>;
 
     /// Creates and initialize the product object.
     ///
@@ -54,6 +81,27 @@ pub trait Scrappable {
     /// # Returns
     /// Tone - The individual tone.
     fn create_tone(element: &ElementRef) -> Tone;
+
+    /// Retrieves the product of a product page of the website.
+    ///
+    /// # Arguments
+    /// url - The url of the product page.
+    /// # Returns
+    /// Product - The product of the page, with the url it was redirected to as link.
+    /// Err - If the page could not be retrieved.
+    fn look_up_product(&self, url: &str) -> Result<Product, anyhow::Error>
+    where
+        Self: Sized,
+    {
+        let response = http::get(url)?;
+        let link = response.url().to_string();
+        let document = Html::parse_document(&response.text()?);
+        let mut product = Self::create_product(&document);
+        product.link = link;
+        // The product was not searched, it is exactly the one requested.
+        product.similarity = 1.0;
+        Ok(product)
+    }
 }
 
 mod test {
@@ -0,0 +1,280 @@
+//! Utilities to compare the names found in the websites with the names searched by the user.
+
+use serde::Serialize;
+
+use crate::colour::ColourMode;
+use crate::helper::utilities;
+
+/// How the similarity of a product was computed.
+#[derive(Clone, Copy, PartialEq, PartialOrd, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SimilarityStrategy {
+    /// Jaro-Winkler between the brand and name of the product and the search.
+    FullName,
+    /// Jaro-Winkler between the name of the product and the search without brand,
+    /// used when the brand of the search is applied as a filter.
+    NameOnly,
+}
+
+/// Match between a word of the search and the closest word of the product.
+#[derive(Clone, PartialEq, PartialOrd, Debug, Serialize)]
+pub struct TokenMatch {
+    /// The word of the search.
+    pub query_token: String,
+    /// The closest word in the product, None if the product has no words.
+    pub title_token: Option<String>,
+    /// The similarity between both words.
+    pub score: f32,
+}
+
+/// Breakdown of how the similarity of a product was computed.
+#[derive(Clone, PartialEq, PartialOrd, Debug, Serialize)]
+pub struct SimilarityExplanation {
+    /// The best-matching variant of the search, normalized as in `normalize`.
+    pub normalized_query: String,
+    /// The product name, normalized as in `normalize`.
+    pub normalized_title: String,
+    /// The closest product word for every word of the search.
+    pub token_matches: Vec<TokenMatch>,
+    /// How the similarity was computed.
+    pub strategy: SimilarityStrategy,
+    /// Similarity gained by scoring only the name when the brand is used as a filter.
+    pub brand_bonus: f32,
+    /// The final similarity.
+    pub score: f32,
+}
+
+impl SimilarityExplanation {
+    /// Formats the explanation to be pretty printed in terminal under the product.
+    /// # Arguments
+    /// colour_mode: In plain mode the tokens are written with ASCII symbols.
+    /// # Example
+    /// * Similarity 91.23% (name only, Jaro-Winkler)
+    ///   query: "colorete" | title: "soft pinch colorete"
+    ///   tokens: colorete→colorete 100% · rosa→∅ 0%
+    ///   plain tokens: colorete->colorete 100% | rosa->- 0%
+    ///   brand bonus: +12.50%
+    pub fn terminal_format(&self, colour_mode: ColourMode) -> String {
+        let strategy = match self.strategy {
+            SimilarityStrategy::FullName => "brand and name",
+            SimilarityStrategy::NameOnly => "name only",
+        };
+        let (arrow, separator, missing) = match colour_mode {
+            ColourMode::Plain => ("->", " | ", "-"),
+            _ => ("→", " · ", "∅"),
+        };
+        let tokens = self
+            .token_matches
+            .iter()
+            .map(|token_match| {
+                format!(
+                    "{}{arrow}{} {:.0}%",
+                    token_match.query_token,
+                    token_match.title_token.as_deref().unwrap_or(missing),
+                    token_match.score * 100.0
+                )
+            })
+            .collect::<Vec<String>>()
+            .join(separator);
+
+        let mut out: String = format!(
+            "    * Similarity {:.2}% ({strategy}, Jaro-Winkler)",
+            self.score * 100.0
+        );
+        out.push_str(
+            format!(
+                "\n      query: \"{}\" | title: \"{}\"",
+                self.normalized_query, self.normalized_title
+            )
+            .as_str(),
+        );
+        out.push_str(format!("\n      tokens: {tokens}").as_str());
+        out.push_str(format!("\n      brand bonus: {:+.2}%", self.brand_bonus * 100.0).as_str());
+        out
+    }
+}
+
+/// Returns the text in lowercase, without accents and with single spaces between words.
+///
+/// # Arguments
+/// text - The text to normalize.
+///
+/// # Returns
+/// The normalized text.
+///
+/// # Example
+/// assert_eq!(normalize("  Máscara  de Pestañas "), "mascara de pestanas");
+pub fn normalize(text: &str) -> String {
+    text.to_lowercase()
+        .chars()
+        .map(|character| match character {
+            'á' | 'à' | 'ä' | 'â' => 'a',
+            'é' | 'è' | 'ë' | 'ê' => 'e',
+            'í' | 'ì' | 'ï' | 'î' => 'i',
+            'ó' | 'ò' | 'ö' | 'ô' => 'o',
+            'ú' | 'ù' | 'ü' | 'û' => 'u',
+            'ñ' => 'n',
+            'ç' => 'c',
+            _ => character,
+        })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
+
+/// Returns the highest similarity between a name and any of the variants of a search.
+///
+/// # Arguments
+/// name - The name of the product found.
+/// variants - The different queries used to find the product.
+///
+/// # Returns
+/// The similarity with the best-matching variant, 0 if there are no variants.
+pub fn best_similarity(name: &str, variants: &[String]) -> f32 {
+    variants
+        .iter()
+        .map(|variant| utilities::compare_similarity(name, variant))
+        .fold(0.0, f32::max)
+}
+
+/// Returns the similarity with the best-matching variant and how it was computed.
+///
+/// # Arguments
+/// name - The name of the product found.
+/// variants - The different queries used to find the product.
+/// strategy - How the name was built, to be reported in the explanation.
+///
+/// # Returns
+/// The explanation, with the first variant if none of them is similar,
+/// and an empty query with a score of 0 if there are no variants.
+pub fn explain(
+    name: &str,
+    variants: &[String],
+    strategy: SimilarityStrategy,
+) -> SimilarityExplanation {
+    let normalized_title = normalize(name);
+    let (normalized_query, score) = variants
+        .iter()
+        .map(|variant| {
+            (
+                normalize(variant),
+                utilities::compare_similarity(name, variant),
+            )
+        })
+        .reduce(|best, current| if current.1 > best.1 { current } else { best })
+        .unwrap_or_default();
+
+    let token_matches = normalized_query
+        .split_whitespace()
+        .map(|query_token| {
+            let best_token = normalized_title
+                .split_whitespace()
+                .map(|title_token| {
+                    (
+                        title_token,
+                        utilities::compare_similarity(query_token, title_token),
+                    )
+                })
+                .fold(None, |best: Option<(&str, f32)>, current| match best {
+                    Some(best) if best.1 >= current.1 => Some(best),
+                    _ => Some(current),
+                });
+            TokenMatch {
+                query_token: query_token.to_string(),
+                title_token: best_token.map(|(token, _)| token.to_string()),
+                score: best_token.map_or(0.0, |(_, score)| score),
+            }
+        })
+        .collect();
+
+    SimilarityExplanation {
+        normalized_query,
+        normalized_title,
+        token_matches,
+        strategy,
+        brand_bonus: 0.0,
+        score,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests the normalize function.
+    #[test]
+    fn normalize_all_cases() {
+        assert_eq!(normalize("Labial"), "labial");
+        assert_eq!(normalize("  Máscara  de Pestañas "), "mascara de pestanas");
+        assert_eq!(normalize("RÍMEL"), "rimel");
+        assert_eq!(normalize(""), "");
+    }
+
+    /// Tests the best_similarity function.
+    #[test]
+    fn best_similarity_all_cases() {
+        let variants = vec![String::from("lipstick"), String::from("labial")];
+        assert_eq!(best_similarity("labial", &variants), 1.0);
+        assert!(best_similarity("labial mate", &variants) > best_similarity("lipstick mate", &[]));
+        assert_eq!(best_similarity("labial", &[]), 0.0);
+    }
+
+    /// Tests the explain function picks the best variant and matches the tokens.
+    #[test]
+    fn explain_all_cases() {
+        let variants = vec![String::from("blush rosa"), String::from("Colorete rosa")];
+        let explanation = explain(
+            "Colorete Rosa Soft",
+            &variants,
+            SimilarityStrategy::NameOnly,
+        );
+        assert_eq!(explanation.normalized_query, "colorete rosa");
+        assert_eq!(explanation.normalized_title, "colorete rosa soft");
+        assert_eq!(
+            explanation.score,
+            best_similarity("Colorete Rosa Soft", &variants)
+        );
+        assert_eq!(explanation.strategy, SimilarityStrategy::NameOnly);
+        assert_eq!(
+            explanation.token_matches,
+            vec![
+                TokenMatch {
+                    query_token: String::from("colorete"),
+                    title_token: Some(String::from("colorete")),
+                    score: 1.0
+                },
+                TokenMatch {
+                    query_token: String::from("rosa"),
+                    title_token: Some(String::from("rosa")),
+                    score: 1.0
+                }
+            ]
+        );
+        assert!(explanation
+            .terminal_format(ColourMode::TrueColour)
+            .contains("colorete→colorete 100% · rosa→rosa 100%"));
+        let plain = explanation.terminal_format(ColourMode::Plain);
+        assert!(plain.contains("colorete->colorete 100% | rosa->rosa 100%"));
+        assert!(plain.is_ascii());
+
+        let explanation = explain(
+            "",
+            &[String::from(" Máscara  de Pestañas"), String::from("rímel")],
+            SimilarityStrategy::FullName,
+        );
+        assert_eq!(explanation.normalized_query, "mascara de pestanas");
+        assert_eq!(explanation.score, 0.0);
+        let explanation = explain(
+            "Máscara de PESTAÑAS",
+            &variants,
+            SimilarityStrategy::FullName,
+        );
+        assert_eq!(explanation.normalized_title, "mascara de pestanas");
+
+        let explanation = explain("", &[], SimilarityStrategy::FullName);
+        assert_eq!(explanation.score, 0.0);
+        assert!(explanation.token_matches.is_empty());
+        println!("{}", explanation.terminal_format(ColourMode::Plain));
+    }
+}
@@ -0,0 +1,145 @@
+//! Bilingual (Spanish/English) makeup dictionary used to expand the search queries.
+//!
+//! The websites we scrap are Spanish, so a search like "lipstick" does not find anything.
+//! We translate the product type found in the query to every synonym we know and search all of them.
+
+use crate::similarity;
+
+/// Maximum number of queries, the original one included, generated from a single search.
+/// Every variant means a new search in every website, so we keep them limited.
+pub const MAX_VARIANTS: usize = 4;
+
+/// Groups of terms that refer to the same kind of product.
+/// The first term of every group is the one used by the websites in most cases.
+pub const SYNONYMS: &[&[&str]] = &[
+    &["labial", "barra de labios", "pintalabios", "lipstick"],
+    &["brillo de labios", "gloss", "lip gloss"],
+    &["perfilador de labios", "lip liner"],
+    &["bálsamo labial", "lip balm"],
+    &["colorete", "blush", "rubor"],
+    &["corrector", "concealer"],
+    &["base de maquillaje", "foundation"],
+    &["prebase", "primer"],
+    &["polvos", "powder"],
+    &["iluminador", "highlighter"],
+    &["bronceador", "bronzer"],
+    &["contorno", "contour"],
+    &["máscara de pestañas", "rímel", "mascara"],
+    &["delineador", "eyeliner"],
+    &["sombra de ojos", "eyeshadow"],
+    &["paleta", "palette"],
+    &["cejas", "eyebrow", "brow"],
+    &["esmalte de uñas", "nail polish"],
+    &["desmaquillante", "makeup remover"],
+    &["spray fijador", "setting spray"],
+    &["brocha", "brush"],
+];
+
+/// Returns the different queries to search for the query provided.
+///
+/// # Arguments
+/// query - The query provided by the user.
+///
+/// # Returns
+/// A vector with the original query first and the translated variants after it, up to MAX_VARIANTS.
+///
+/// # Example
+/// let variants = expand_query("rare beauty blush");
+/// assert_eq!(variants, vec!["rare beauty blush", "rare beauty colorete", "rare beauty rubor"]);
+pub fn expand_query(query: &str) -> Vec<String> {
+    let mut variants: Vec<String> = vec![query.to_string()];
+    let normalized_query = similarity::normalize(query);
+
+    for group in SYNONYMS {
+        // Look for the longest term so "barra de labios" is replaced instead of a shorter term inside it.
+        let term = group
+            .iter()
+            .map(|term| similarity::normalize(term))
+            .filter(|term| contains_term(&normalized_query, term))
+            .max_by_key(|term| term.len());
+
+        if let Some(term) = term {
+            for synonym in group.iter() {
+                if similarity::normalize(synonym) == term {
+                    continue;
+                }
+                let variant = replace_term(&normalized_query, &term, synonym);
+                if !variants.contains(&variant) {
+                    variants.push(variant);
+                }
+                if variants.len() == MAX_VARIANTS {
+                    return variants;
+                }
+            }
+        }
+    }
+    variants
+}
+
+/// Returns true if the normalized text contains the normalized term as complete words.
+fn contains_term(text: &str, term: &str) -> bool {
+    format!(" {text} ").contains(&format!(" {term} "))
+}
+
+/// Returns the normalized text with the first appearance of the term replaced by the synonym.
+fn replace_term(text: &str, term: &str, synonym: &str) -> String {
+    format!(" {text} ")
+        .replacen(&format!(" {term} "), &format!(" {synonym} "), 1)
+        .trim()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests a query in English is translated to Spanish.
+    #[test]
+    fn expand_query_english_to_spanish() {
+        assert_eq!(
+            expand_query("Rare Beauty blush"),
+            vec![
+                "Rare Beauty blush",
+                "rare beauty colorete",
+                "rare beauty rubor"
+            ]
+        );
+        assert_eq!(expand_query("concealer"), vec!["concealer", "corrector"]);
+    }
+
+    /// Tests a query with a term of several words and accents.
+    #[test]
+    fn expand_query_multiple_words_and_accents() {
+        assert_eq!(
+            expand_query("Barra de Labios mate"),
+            vec![
+                "Barra de Labios mate",
+                "labial mate",
+                "pintalabios mate",
+                "lipstick mate"
+            ]
+        );
+        assert_eq!(
+            expand_query("rimel waterproof"),
+            vec![
+                "rimel waterproof",
+                "máscara de pestañas waterproof",
+                "mascara waterproof"
+            ]
+        );
+    }
+
+    /// Tests a query without any known term is not expanded.
+    #[test]
+    fn expand_query_without_synonyms() {
+        assert_eq!(expand_query("Kind Words"), vec!["Kind Words"]);
+        // Terms must be complete words.
+        assert_eq!(expand_query("blushing"), vec!["blushing"]);
+    }
+
+    /// Tests the number of variants is limited.
+    #[test]
+    fn expand_query_max_variants() {
+        assert_eq!(expand_query("lipstick blush concealer").len(), MAX_VARIANTS);
+    }
+}
@@ -11,15 +11,19 @@
     3.2 - To filter them we get the title in format {Brand} - {Name} - {Tone} and format it to remove the tone part and only store the first of them.
             This way if we find 4 items that are 1 but with four different tones, we will only store the first one and redirect to it.
     3.3 - We get the full name with "h3.Title>a" and the URL with the "href" attribute.
+4 - In the product page we split the title in brand, name and tone with the title module.
+    4.1 - We use the brand link "a.Marca" and the selected tone in "ul.familasColores>li.active>a" when they match the title.
+    4.2 - Otherwise we split by " - " since brands and names can contain dashes without spaces ("Anti-Age").
 */
 #![allow(clippy::field_reassign_with_default)]
 
 use std::thread::JoinHandle;
 
+use crate::colour::Rgb;
 use crate::configuration::Configuration;
-use crate::helper::{scrapping, utilities};
+use crate::helper::{http, scrapping, utilities};
 use crate::product::{Product, Tone};
-use crate::scrappable::{Scrappable, SearchError};
+use crate::scrappable::{Scrappable, SearchError, SearchResults};
 
 // Webpage url.
 const URL: &str = "https://www.maquillalia.com/";
@@ -31,6 +35,10 @@ const PAGINATION_SUFFIX: &str = "page=";
 const ITEMS_PER_PAGE: usize = 20;
 // Maximum rating for SephoraSpain.
 const MAX_RATING: f32 = 5.0;
+// Confidence below which we warn about a title that might not be split correctly.
+const MIN_TITLE_CONFIDENCE: f32 = 0.5;
+// Swatch of the tone selected in the tone page.
+const SELECTED_SWATCH_SELECTOR: &str = "ul.familasColores>li.active";
 
 /// Structure that define functionality for SephoraSpain.
 pub struct Maquillalia<'a> {
@@ -48,36 +56,189 @@ impl<'a> Maquillalia<'a> {
     /// let v: String = get_name_without_tone(String::from("Maybelline - Labial líquido SuperStay Vinyl Ink - 35: Pink"));
     /// assert_eq!(v, "Maybelline - Labial líquido SuperStay Vinyl Ink");
     pub fn get_name_without_tone(full_name: &str) -> String {
-        let mut splitted_name = full_name.split('-');
-        let full_name =
-            splitted_name.next().unwrap().to_string() + splitted_name.next().unwrap().trim_end();
-        full_name.replace("  ", " - ")
+        let parsed_title = title::parse(full_name, &title::TitleHints::default());
+        match parsed_title.brand {
+            Some(brand) => format!("{} - {}", brand, parsed_title.name),
+            None => parsed_title.name,
+        }
     }
     /// Returns the name of the tone.
+    /// # Example
+    /// let v: String = get_tone_name(String::from("Maybelline - Labial líquido SuperStay Vinyl Ink - 35: Pink"));
+    /// assert_eq!(v, "35: Pink");
     pub fn get_tone_name(full_name: &str) -> String {
-        let mut splitted_name = full_name.split('-');
-        splitted_name.next().unwrap();
-        splitted_name.next().unwrap();
-        splitted_name.collect()
+        title::parse(full_name, &title::TitleHints::default())
+            .tone
+            .unwrap_or_default()
+    }
+}
+
+/// Parsing of the titles in format {Brand} - {Name} - {Tone} used by Maquillalia.
+///
+/// Brands and names can contain dashes ("Anti-Age", "Long-wear") and tones can contain the separator too,
+/// so we use the brand link and the tone selector of the page first and a heuristic over the " - " separator second.
+pub mod title {
+    use crate::helper::scrapping;
+    use crate::similarity;
+
+    /// Separator between the brand, the name and the tone in the titles.
+    const SEPARATOR: &str = " - ";
+    /// Link to the brand page in the product page.
+    const BRAND_SELECTOR: &str = "a.Marca";
+    /// Tone selected in the tone selector of the product page.
+    const SELECTED_TONE_SELECTOR: &str = "ul.familasColores>li.active>a";
+    /// Tone selector of the product page.
+    const TONES_SELECTOR: &str = "ul.familasColores>li";
+
+    /// Brand, name and tone extracted from a title.
+    #[derive(Clone, Debug, Default, PartialEq)]
+    pub struct ParsedTitle {
+        /// The brand, None if the title does not have one.
+        pub brand: Option<String>,
+        /// The name of the product.
+        pub name: String,
+        /// The tone, None if the title does not have one.
+        pub tone: Option<String>,
+        /// Confidence between 0 and 1 of the title being split correctly.
+        pub confidence: f32,
+    }
+
+    /// Information from the page structure that helps to split the title.
+    #[derive(Clone, Debug, Default, PartialEq)]
+    pub struct TitleHints {
+        /// The brand from the brand link of the page.
+        pub brand: Option<String>,
+        /// The tone selected in the tone selector of the page.
+        pub tone: Option<String>,
+        /// If the page has a tone selector, None if unknown like in the search results.
+        pub has_tones: Option<bool>,
+    }
+
+    /// Returns the hints found in a product page.
+    /// # Arguments
+    /// html - The HTML element of the product page.
+    pub fn hints(html: &scraper::ElementRef) -> TitleHints {
+        let not_empty = |text: String| {
+            let text = text.trim().to_string();
+            (!text.is_empty()).then_some(text)
+        };
+        TitleHints {
+            brand: scrapping::inner_html_value(html, BRAND_SELECTOR)
+                .ok()
+                .and_then(not_empty),
+            tone: scrapping::attribute_html_value(html, SELECTED_TONE_SELECTOR, "title")
+                .ok()
+                .and_then(not_empty),
+            has_tones: Some(scrapping::has_html_selector(html, TONES_SELECTOR)),
+        }
+    }
+
+    /// Splits a title in brand, name and tone.
+    /// # Arguments
+    /// title - The title in format {Brand} - {Name} - {Tone}.
+    /// hints - The information from the page structure.
+    /// # Returns
+    /// The parsed title with the confidence of the result.
+    /// # Example
+    /// let parsed = parse("Maybelline - Labial SuperStay - 35: Cheeky", &TitleHints::default());
+    /// assert_eq!(parsed.brand.unwrap(), "Maybelline");
+    /// assert_eq!(parsed.name, "Labial SuperStay");
+    /// assert_eq!(parsed.tone.unwrap(), "35: Cheeky");
+    pub fn parse(title: &str, hints: &TitleHints) -> ParsedTitle {
+        let title = title.split_whitespace().collect::<Vec<&str>>().join(" ");
+        let mut confidence: f32 = 1.0;
+
+        // Brand: the brand link if the title starts with it, otherwise the first segment.
+        let brand_hint = hints.brand.as_ref().and_then(|brand| {
+            strip_prefix(&title, &format!("{brand}{SEPARATOR}")).map(|rest| (brand, rest))
+        });
+        let (brand, rest) = match brand_hint {
+            Some((brand, rest)) => (Some(brand.clone()), rest),
+            None => match title.split_once(SEPARATOR) {
+                Some((brand, rest)) => {
+                    confidence *= 0.8;
+                    (Some(brand.to_string()), rest.to_string())
+                }
+                None => {
+                    confidence *= 0.4;
+                    (hints.brand.clone(), title.clone())
+                }
+            },
+        };
+
+        // Tone: the selected tone if the title ends with it, otherwise everything after the name.
+        let tone_hint = hints.tone.as_ref().and_then(|tone| {
+            strip_suffix(&rest, &format!("{SEPARATOR}{tone}")).map(|name| (name, tone))
+        });
+        let (name, tone) = match tone_hint {
+            Some((name, tone)) => (name, Some(tone.clone())),
+            // Without tone selector everything is part of the name.
+            None if hints.has_tones == Some(false) => (rest, None),
+            None => match rest.split_once(SEPARATOR) {
+                Some((name, tone)) => {
+                    // The tone could also contain the separator or belong to the name.
+                    confidence *= if tone.contains(SEPARATOR) { 0.6 } else { 0.9 };
+                    if hints.has_tones.is_none() {
+                        confidence *= 0.9;
+                    }
+                    (name.to_string(), Some(tone.to_string()))
+                }
+                None => {
+                    if hints.has_tones == Some(true) {
+                        confidence *= 0.6;
+                    }
+                    (rest, None)
+                }
+            },
+        };
+
+        ParsedTitle {
+            brand,
+            name,
+            tone,
+            confidence,
+        }
+    }
+
+    /// Returns the text without the prefix, comparing without case and accents.
+    fn strip_prefix(text: &str, prefix: &str) -> Option<String> {
+        let boundary = text.char_indices().nth(prefix.chars().count())?.0;
+        (similarity::normalize(&text[..boundary]) == similarity::normalize(prefix))
+            .then(|| text[boundary..].trim().to_string())
+    }
+
+    /// Returns the text without the suffix, comparing without case and accents.
+    fn strip_suffix(text: &str, suffix: &str) -> Option<String> {
+        let suffix_length = suffix.chars().count();
+        let text_length = text.chars().count();
+        if suffix_length >= text_length {
+            return None;
+        }
+        let boundary = text.char_indices().nth(text_length - suffix_length)?.0;
+        (similarity::normalize(&text[boundary..]) == similarity::normalize(suffix))
+            .then(|| text[..boundary].trim().to_string())
     }
 }
 
 /// Scrappable trait implementation for Maquillalia.
 impl<'a> Scrappable for Maquillalia<'a> {
-    fn look_for_products(&self, name: String) -> Result<Vec<Product>, anyhow::Error> {
+    fn search(&self, name: String) -> Result<SearchResults, anyhow::Error> {
         // We receive a word like "This word" and we should search in format of "This+word".
         let formatted_name = name.replace(' ', "+");
         let mut page: usize = 1;
         let mut is_last_page: bool = false;
         let mut products = Vec::<Product>::new();
         let mut products_urls: Vec<String> = vec![];
+        let mut found: Vec<String> = vec![];
+        // The error of the first page without results, returned if no page has any.
+        let mut search_error: Option<anyhow::Error> = None;
 
         // We have to search for all the pages to retrieve the products.
         // TODO: Im not sure if i can parallelize and preserve the order of insertion in the products_urls vector in order by page.
         while !is_last_page {
             let query = format!("{URL}{SEARCH_SUFFIX}{formatted_name}&{PAGINATION_SUFFIX}{page}");
 
-            let response = reqwest::blocking::get(query)?;
+            let response = http::get(&query)?;
             let document = scraper::Html::parse_document(&response.text()?);
             let total_results: usize =
                 match scrapping::inner_html_value(&document.root_element(), "div.NumPro>strong") {
@@ -88,9 +249,15 @@ impl<'a> Scrappable for Maquillalia<'a> {
                     }
                 };
             // Get the urls for all the coincidence we found in the search with the given `name`
-            let page_products_urls = self.search_results_urls(&document, name.as_str())?;
-            for product_url in page_products_urls {
-                products_urls.push(product_url);
+            // A page can have only products of other brands or not similar enough, so we keep looking in the next ones.
+            match self.search_results_urls(&document, name.as_str()) {
+                Ok(page_results) => {
+                    products_urls.extend(page_results.similar);
+                    found.extend(page_results.found);
+                }
+                Err(err) => {
+                    search_error.get_or_insert(err);
+                }
             }
 
             page += 1;
@@ -104,6 +271,12 @@ impl<'a> Scrappable for Maquillalia<'a> {
             }
         }
 
+        if found.is_empty() {
+            if let Some(err) = search_error {
+                return Err(err);
+            }
+        }
+
         // Use threads to perform concurrency when sending petitions.
         let mut handles = Vec::<JoinHandle<Option<Product>>>::new();
         for url in products_urls {
@@ -115,7 +288,7 @@ impl<'a> Scrappable for Maquillalia<'a> {
                     .spawn(move || -> Option<Product> {
                         let response: String;
                         // TODO: Maybe add some logging in case of returning None
-                        if let Ok(http_response) = reqwest::blocking::get(&url) {
+                        if let Ok(http_response) = http::get(&url) {
                             if let Ok(text) = http_response.text() {
                                 response = text;
                             } else {
@@ -127,8 +300,7 @@ impl<'a> Scrappable for Maquillalia<'a> {
                         let document = scraper::Html::parse_document(&response);
                         let mut product: Product = Self::create_product(&document);
                         product.link = url;
-                        let full_name =
-                            format!("{} {}", product.brand.as_ref().unwrap(), product.name);
+                        let full_name = product.full_name();
                         product.similarity =
                             utilities::compare_similarity(full_name.as_str(), name_copy.as_str());
                         Some(product)
@@ -141,15 +313,19 @@ impl<'a> Scrappable for Maquillalia<'a> {
                 products.push(product);
             }
         }
-        Ok(products)
+        Ok(SearchResults {
+            similar: products,
+            found,
+        })
     }
 
     fn search_results_urls(
         &self,
         document: &scraper::Html,
         name: &str,
-    ) -> Result<Vec<String>, anyhow::Error> {
+    ) -> Result<SearchResults<String>, anyhow::Error> {
         let mut urls: Vec<String> = Vec::new();
+        let mut found: Vec<String> = Vec::new();
         let mut any_results = false;
 
         // Check if we find the flag that indicates that we did not find any results.
@@ -166,15 +342,25 @@ impl<'a> Scrappable for Maquillalia<'a> {
         let mut individual_products: Vec<String> = Vec::new();
 
         for item in items {
+            // The page has results even if all of them are of other brands.
+            any_results = true;
             // In the search page we have all the tones for a product so we will only store one of them and skip the rest because they are separated in the las dash({Brand} - {Name} - {Tone}).
             // Name format is {Brand} - {Name} - {Tone}
-            let full_name = scrapping::inner_html_value(&item, "h3.Title>a").map_or_else(
+            let parsed_title = scrapping::inner_html_value(&item, "h3.Title>a").map_or_else(
                 |err| {
-                    eprintln!("Full name not found, assigning String::new(): {:?}", err);
-                    String::new()
+                    eprintln!("Full name not found, assigning an empty title: {:?}", err);
+                    title::ParsedTitle::default()
                 },
-                |element_name| Maquillalia::get_name_without_tone(&element_name),
+                |element_name| title::parse(&element_name, &title::TitleHints::default()),
             );
+            // Skip the products of other brands before retrieving their pages.
+            if !self.config.accepts_brand(parsed_title.brand.as_deref()) {
+                continue;
+            }
+            let full_name = match parsed_title.brand {
+                Some(brand) => format!("{} - {}", brand, parsed_title.name),
+                None => parsed_title.name,
+            };
             let url = match scrapping::attribute_html_value(&item, "h3.Title>a", "href") {
                 Ok(url) => Some(url),
                 Err(err) => {
@@ -182,28 +368,27 @@ impl<'a> Scrappable for Maquillalia<'a> {
                     None
                 }
             };
-            any_results = true;
 
+            // If we already have the product name, we skip the product because must be a tone of that product.
+            if individual_products.contains(&full_name) {
+                continue;
+            }
             let similarity = utilities::compare_similarity(name, &full_name);
-            if similarity >= self.config.min_similarity() {
-                // If we already have the product name, we skip the product because must be a tone of that product.
-                if individual_products.contains(&full_name) {
-                    continue;
-                }
-
-                individual_products.push(full_name);
-                if let Some(url) = url {
+            individual_products.push(full_name);
+            if let Some(url) = url {
+                found.push(url.clone());
+                if similarity >= self.config.min_similarity() {
                     urls.push(url);
                 }
             }
         }
 
-        if any_results && !urls.is_empty() {
-            Ok(urls)
-        } else if any_results && urls.is_empty() {
-            Err(anyhow::anyhow!(SearchError::NotEnoughSimilarity))
-        } else {
-            Err(anyhow::anyhow!(SearchError::NotFound))
+        match any_results {
+            true => Ok(SearchResults {
+                similar: urls,
+                found,
+            }),
+            false => Err(anyhow::anyhow!(SearchError::NotFound)),
         }
     }
 
@@ -211,31 +396,25 @@ impl<'a> Scrappable for Maquillalia<'a> {
         let mut product = Product::default();
         let html = document.root_element();
 
-        // Get full name and remove tone
-        let full_name = scrapping::inner_html_value(&html, "h1.Title").map_or_else(
-            |err| {
-                eprintln!("Text not found, assigning String::new(): {:?}", err);
-                String::new()
-            },
-            |text| Maquillalia::get_name_without_tone(&text),
-        );
-
-        // TODO: Remove trailing and beginning white spaces.
-        let mut name_and_brand = full_name.trim().split('-');
-
-        // Assign brand and name values to product, with error handling
-        match (name_and_brand.next(), name_and_brand.next()) {
-            (Some(brand), Some(name)) => {
-                product.brand = Some(brand.to_string());
-                product.name = name.to_string();
-            }
-            _ => {
-                eprintln!(
-                    "Error: could not extract brand and name from product name: {}",
-                    full_name
-                );
-                // Return an error value here, or handle the error in some other way.
+        // Split the title in brand, name and tone using the page structure when possible.
+        match scrapping::inner_html_value(&html, "h1.Title") {
+            Ok(full_name) => {
+                let parsed_title = title::parse(&full_name, &title::hints(&html));
+                if parsed_title.brand.is_none() {
+                    eprintln!(
+                        "Error: could not extract brand and name from product name: {}",
+                        full_name
+                    );
+                } else if parsed_title.confidence < MIN_TITLE_CONFIDENCE {
+                    eprintln!(
+                        "Warning: low confidence ({}) splitting product name: {}",
+                        parsed_title.confidence, full_name
+                    );
+                }
+                product.brand = parsed_title.brand;
+                product.name = parsed_title.name;
             }
+            Err(err) => eprintln!("Text not found, assigning String::new(): {:?}", err),
         }
 
         // If we find the element for different tones we iterate over all the websites and fill the Tone variable.
@@ -245,7 +424,13 @@ impl<'a> Scrappable for Maquillalia<'a> {
         for url in tones_urls {
             // TODO: Try to parallelize in the future.
             if let Ok(url_string) = scrapping::attribute_html_value(&url, "a", "href") {
-                let response = reqwest::blocking::get(&url_string).unwrap().text().unwrap();
+                let response = match http::get(&url_string).and_then(|response| response.text()) {
+                    Ok(response) => response,
+                    Err(err) => {
+                        eprintln!("Tone not retrieved from {url_string}: {:?}", err);
+                        continue;
+                    }
+                };
                 let document = scraper::Html::parse_document(&response);
                 let mut tone = Self::create_tone(&document.root_element());
                 tone.url = Some(url_string);
@@ -310,7 +495,7 @@ impl<'a> Scrappable for Maquillalia<'a> {
                 eprintln!("Tone.name not found, assigning None: {:?}", err);
                 None
             },
-            |name| Some(Maquillalia::get_tone_name(&name).trim().to_string()),
+            |name| title::parse(&name, &title::hints(element)).tone,
         );
 
         if let Ok(price_standard) =
@@ -350,6 +535,11 @@ impl<'a> Scrappable for Maquillalia<'a> {
                         ))
                     },
                 );
+        // The swatch of the tone is the selected one in the list of tones.
+        tone.colour = element
+            .select(&scraper::Selector::parse(SELECTED_SWATCH_SELECTOR).unwrap())
+            .next()
+            .and_then(|swatch| Rgb::from_element(&swatch));
         tone
     }
 }
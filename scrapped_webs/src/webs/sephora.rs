@@ -3,12 +3,13 @@
 use std::thread;
 use std::thread::JoinHandle;
 
+use crate::colour::Rgb;
 use crate::configuration::Configuration;
-use crate::helper::{scrapping, utilities};
+use crate::helper::{http, scrapping, utilities};
 use crate::{
     product::Product,
     product::Tone,
-    scrappable::{Scrappable, SearchError},
+    scrappable::{Scrappable, SearchError, SearchResults},
 };
 use scraper::ElementRef;
 use scraper::Html;
@@ -62,28 +63,32 @@ pub mod spain {
 
     /// Scrappable trait implementation for SephoraSpain.
     impl<'a> Scrappable for SephoraSpain<'a> {
-        fn look_for_products(&self, name: String) -> Result<Vec<Product>, anyhow::Error> {
+        fn search(&self, name: String) -> Result<SearchResults, anyhow::Error> {
             // We receive a word like "This word" and we should search in format of "This+word".
             let formatted_name = name.replace(' ', "+");
             let query = format!("{URL}{SEARCH_SUFFIX}{formatted_name}");
 
             // If the name match exactly, SephoraSpain redirects you to the product page.
-            let response = reqwest::blocking::get(query)?;
+            let response = http::get(&query)?;
             let response_url = response.url().to_owned();
             let document = scraper::Html::parse_document(&response.text()?);
             let mut products = Vec::<Product>::new();
+            let found: Vec<String>;
 
             // If it only find 1 result it redirects to a product page directly with /p/product_link.html
             if response_url.as_str().contains("/p/") {
                 let mut product = SephoraSpain::create_product(&document);
                 product.link = response_url.to_string();
-                let full_name = format!("{} {}", product.brand.as_ref().unwrap(), product.name);
+                let full_name = product.full_name();
                 product.similarity =
                     utilities::compare_similarity(full_name.as_str(), name.as_str());
+                found = vec![product.link.clone()];
                 products.push(product);
             } else {
                 // Get the urls for all the coincidence we found in the search with the given `name`
-                let products_urls = self.search_results_urls(&document, name.as_str())?;
+                let results_urls = self.search_results_urls(&document, name.as_str())?;
+                found = results_urls.found;
+                let products_urls = results_urls.similar;
 
                 // Use threads to perform concurrency when sending petitions.
                 let mut handles = Vec::<JoinHandle<Option<Product>>>::new();
@@ -95,7 +100,7 @@ pub mod spain {
                             .spawn(move || -> Option<Product> {
                                 let response: String;
                                 // TODO: Maybe add some logging in case of returning None
-                                if let Ok(http_response) = reqwest::blocking::get(&url) {
+                                if let Ok(http_response) = http::get(&url) {
                                     if let Ok(text) = http_response.text() {
                                         response = text;
                                     } else {
@@ -107,8 +112,7 @@ pub mod spain {
                                 let document = scraper::Html::parse_document(&response);
                                 let mut product: Product = SephoraSpain::create_product(&document);
                                 product.link = url;
-                                let full_name =
-                                    format!("{} {}", product.brand.as_ref().unwrap(), product.name);
+                                let full_name = product.full_name();
                                 product.similarity = utilities::compare_similarity(
                                     full_name.as_str(),
                                     name_copy.as_str(),
@@ -125,15 +129,19 @@ pub mod spain {
                 }
             }
 
-            Ok(products)
+            Ok(SearchResults {
+                similar: products,
+                found,
+            })
         }
 
         fn search_results_urls(
             &self,
             document: &Html,
             name: &str,
-        ) -> Result<Vec<String>, anyhow::Error> {
+        ) -> Result<SearchResults<String>, anyhow::Error> {
             let mut urls: Vec<String> = Vec::new();
+            let mut found: Vec<String> = Vec::new();
             let mut any_results = false;
 
             // Select the div that wraps the information for every result found.
@@ -146,12 +154,21 @@ pub mod spain {
             let mut num_results = 0;
 
             for item in items {
+                // The page has results even if all of them are of other brands.
                 any_results = true;
                 let brand = scrapping::inner_html_value(&item, "span.product-brand")
                     .unwrap_or_else(|err| {
                         eprintln!("Brand not found, assigning String::new(): {:?}", err);
                         String::new()
                     });
+                // Skip the products of other brands before retrieving their pages.
+                let trimmed_brand = brand.trim();
+                if !self
+                    .config
+                    .accepts_brand((!trimmed_brand.is_empty()).then_some(trimmed_brand))
+                {
+                    continue;
+                }
 
                 let title =
                     scrapping::attribute_html_value(&item, "h3", "title").unwrap_or_else(|err| {
@@ -168,6 +185,9 @@ pub mod spain {
                 // full_name format = {Brand} {Title} = {Rare Beauty} {Kind Words - Barra de labios mate}
                 let full_name = brand + " " + title.as_str();
                 let similarity = utilities::compare_similarity(name, &full_name);
+                if !url.is_empty() {
+                    found.push(url.clone());
+                }
 
                 if similarity >= self.config.min_similarity() && !url.is_empty() {
                     urls.push(url.to_string());
@@ -178,12 +198,12 @@ pub mod spain {
                 }
             }
 
-            if any_results && !urls.is_empty() {
-                Ok(urls)
-            } else if any_results && urls.is_empty() {
-                Err(anyhow::anyhow!(SearchError::NotEnoughSimilarity))
-            } else {
-                Err(anyhow::anyhow!(SearchError::NotFound))
+            match any_results {
+                true => Ok(SearchResults {
+                    similar: urls,
+                    found,
+                }),
+                false => Err(anyhow::anyhow!(SearchError::NotFound)),
             }
         }
 
@@ -291,7 +311,9 @@ pub mod spain {
             //     }
             // };
 
-            Tone::new(tone_name, price_standard, price_sale, available, None, None)
+            let mut tone = Tone::new(tone_name, price_standard, price_sale, available, None, None);
+            tone.colour = Rgb::from_element(element);
+            tone
         }
     }
 }
@@ -1,7 +1,9 @@
 #[cfg(test)]
+#[allow(clippy::assertions_on_constants)]
 mod maquillalia {
     use scrapped_webs::configuration::Configuration;
     use scrapped_webs::scrappable::*;
+    use scrapped_webs::webs::maquillalia::title::{self, ParsedTitle, TitleHints};
     use scrapped_webs::webs::maquillalia::Maquillalia;
 
     /// Tests the creation for Maquillalia structure.
@@ -26,17 +28,135 @@ mod maquillalia {
 
     /// Tests the parsing between the full name in Maquillalia and a custom version with only the final tone name.
     #[test]
-    #[ignore]
     fn get_tone_name() {
         let full_name: String =
             String::from("Maybelline - Labial líquido SuperStay Vinyl Ink - 35: Cheeky");
         let tone_name: String = Maquillalia::get_tone_name(&full_name);
-        assert_eq!(tone_name, " 35: Cheeky");
+        assert_eq!(tone_name, "35: Cheeky");
+
+        let full_name: String = String::from("Maybelline - Labial líquido SuperStay Vinyl Ink - 35: Cheeky - Extra long - dashes - - - - -");
+        let tone_name: String = Maquillalia::get_tone_name(&full_name);
+        assert_eq!(tone_name, "35: Cheeky - Extra long - dashes - - - - -");
+    }
+
+    /// Tests the title parsing without page structure for tricky titles.
+    #[test]
+    fn parse_title_heuristic() {
+        // (title, brand, name, tone)
+        let fixtures = [
+            (
+                "Maybelline - Labial líquido SuperStay Vinyl Ink - 35: Cheeky",
+                Some("Maybelline"),
+                "Labial líquido SuperStay Vinyl Ink",
+                Some("35: Cheeky"),
+            ),
+            (
+                "L'Oréal Paris - Sérum Revitalift Anti-Age - 30 ml",
+                Some("L'Oréal Paris"),
+                "Sérum Revitalift Anti-Age",
+                Some("30 ml"),
+            ),
+            (
+                "Wet n Wild - Base de maquillaje Photo Focus Long-wear - Soft Ivory",
+                Some("Wet n Wild"),
+                "Base de maquillaje Photo Focus Long-wear",
+                Some("Soft Ivory"),
+            ),
+            (
+                "Agrado - Bruma facial solar SPF50+",
+                Some("Agrado"),
+                "Bruma facial solar SPF50+",
+                None,
+            ),
+            (
+                "  Milani  -  Labial Líquido Amore Mettallics ",
+                Some("Milani"),
+                "Labial Líquido Amore Mettallics",
+                None,
+            ),
+            ("Esponja de maquillaje", None, "Esponja de maquillaje", None),
+        ];
+        for (full_name, brand, name, tone) in fixtures {
+            let parsed = title::parse(full_name, &TitleHints::default());
+            assert_eq!(parsed.brand.as_deref(), brand, "{full_name}");
+            assert_eq!(parsed.name, name, "{full_name}");
+            assert_eq!(parsed.tone.as_deref(), tone, "{full_name}");
+            assert!(parsed.confidence > 0.0 && parsed.confidence < 1.0);
+        }
+    }
+
+    /// Tests the title parsing with the brand and the tone from the page structure.
+    #[test]
+    fn parse_title_with_hints() {
+        let hints = TitleHints {
+            brand: Some(String::from("Kiko Milano")),
+            tone: Some(String::from("02 - Rose Gold")),
+            has_tones: Some(true),
+        };
+        let parsed = title::parse(
+            "KIKO MILANO - Barra de labios Long-lasting - Edición limitada - 02 - Rose Gold",
+            &hints,
+        );
+        assert_eq!(
+            parsed,
+            ParsedTitle {
+                brand: Some(String::from("Kiko Milano")),
+                name: String::from("Barra de labios Long-lasting - Edición limitada"),
+                tone: Some(String::from("02 - Rose Gold")),
+                confidence: 1.0,
+            }
+        );
+
+        // A page without tone selector keeps the dashes in the name.
+        let hints = TitleHints {
+            brand: Some(String::from("Isdin")),
+            tone: None,
+            has_tones: Some(false),
+        };
+        let parsed = title::parse("Isdin - Fotoprotector - Fusion Water Magic", &hints);
+        assert_eq!(parsed.brand.as_deref(), Some("Isdin"));
+        assert_eq!(parsed.name, "Fotoprotector - Fusion Water Magic");
+        assert_eq!(parsed.tone, None);
+        assert_eq!(parsed.confidence, 1.0);
+
+        // Ambiguous titles have less confidence than clear ones.
+        let ambiguous = title::parse("Brand - Name - Tone - Extra", &TitleHints::default());
+        let clear = title::parse("Brand - Name - Tone", &TitleHints::default());
+        assert!(ambiguous.confidence < clear.confidence);
+    }
+
+    /// Tests the hints are extracted from the product page.
+    #[test]
+    fn title_hints_from_page() {
+        let html = r#"
+            <!DOCTYPE html>
+            <h1 class="Title">Kiko Milano - Barra de labios - 02 - Rose Gold</h1>
+            <a class="Marca" href="/kiko-milano"> Kiko Milano </a>
+            <ul class="familasColores">
+                <li><a href="/tone-01" title="01 - Nude">01</a></li>
+                <li class="active"><a href="/tone-02" title="02 - Rose Gold">02</a></li>
+            </ul>
+        "#;
+        let document = scraper::Html::parse_document(html);
+        let hints = title::hints(&document.root_element());
+        assert_eq!(
+            hints,
+            TitleHints {
+                brand: Some(String::from("Kiko Milano")),
+                tone: Some(String::from("02 - Rose Gold")),
+                has_tones: Some(true),
+            }
+        );
 
-        //TODO: Support for tones with trailing dashes.
-        // let full_name: String = String::from("Maybelline - Labial líquido SuperStay Vinyl Ink - 35: Cheeky - Extra long - dashes - - - - -");
-        // let tone_name: String = Maquillalia::get_tone_name(&full_name);
-        // assert_eq!(tone_name, " 35: Cheeky - Extra long - dashes - - - - -");
+        let document = scraper::Html::parse_document("<h1 class=\"Title\">Agrado - Bruma</h1>");
+        assert_eq!(
+            title::hints(&document.root_element()),
+            TitleHints {
+                brand: None,
+                tone: None,
+                has_tones: Some(false),
+            }
+        );
     }
 
     /// Tests a search with a few results.
@@ -97,9 +217,9 @@ mod maquillalia {
         assert_eq!(products.len(), 1);
         assert_eq!(
             products.first().unwrap().name,
-            " Labial Líquido Amore Mettallics"
+            "Labial Líquido Amore Mettallics"
         );
-        assert_eq!(products.first().unwrap().brand.as_ref().unwrap(), "Milani ");
+        assert_eq!(products.first().unwrap().brand.as_ref().unwrap(), "Milani");
         assert_eq!(products.first().unwrap().price_standard, None);
         assert_eq!(products.first().unwrap().price_sales, None);
         assert_eq!(products.first().unwrap().rating, None);
@@ -114,11 +234,25 @@ mod maquillalia {
             .look_for_products(String::from("Agrado - Bruma facial solar SPF50+"))
             .unwrap();
         assert_eq!(products.len(), 1);
-        assert_eq!(products.first().unwrap().name, " Bruma facial solar SPF50+");
+        assert_eq!(products.first().unwrap().name, "Bruma facial solar SPF50+");
         assert_eq!(
             products.first().unwrap().brand.as_deref().unwrap(),
-            "Agrado ".to_string()
+            "Agrado".to_string()
         );
-        assert_eq!(products.first().unwrap().tones.is_none(), true);
+        assert!(products.first().unwrap().tones.is_none());
+    }
+
+    /// Tests a search page with only products of other brands has results, none of them kept.
+    #[test]
+    fn search_results_urls_other_brands() {
+        let conf: Configuration =
+            Configuration::new(0.0, 10).with_brands(vec![String::from("Dior")], Vec::new());
+        let document = scraper::Html::parse_document(
+            r#"<div class="ListProds"><div><h3 class="Title"><a href="https://www.maquillalia.com/p/1">NYX - Butter Gloss - Red</a></h3></div></div>"#,
+        );
+        let results = Maquillalia::new(&conf)
+            .search_results_urls(&document, "Butter Gloss")
+            .unwrap();
+        assert_eq!(results, SearchResults::default());
     }
 }
@@ -1,4 +1,5 @@
 #[cfg(test)]
+#[allow(clippy::assertions_on_constants)]
 mod sephora_spain {
     use scrapped_webs::configuration::Configuration;
     use scrapped_webs::scrappable::*;
@@ -26,7 +27,7 @@ mod sephora_spain {
             .unwrap();
         assert_eq!(products.len(), 1);
 
-        let product = products.get(0).unwrap();
+        let product = products.first().unwrap();
         assert_eq!(
             *product.name,
             "SoftSculpt® Shaping Stick - Contorno en barra".to_string()
@@ -126,4 +127,18 @@ mod sephora_spain {
             },
         }
     }
+
+    /// Tests a search page with only products of other brands has results, none of them kept.
+    #[test]
+    fn search_results_urls_other_brands() {
+        let conf: Configuration =
+            Configuration::new(0.0, 10).with_brands(vec![String::from("Dior")], Vec::new());
+        let document = scraper::Html::parse_document(
+            r#"<ul id="search-result-items"><li><div><div class="product-info-wrapper"><div class="product-info"><span class="product-brand">NYX</span></div></div></div></li></ul>"#,
+        );
+        let results = SephoraSpain::new(&conf)
+            .search_results_urls(&document, "Butter Gloss")
+            .unwrap();
+        assert_eq!(results, SearchResults::default());
+    }
 }
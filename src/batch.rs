@@ -0,0 +1,240 @@
+//! Batch of searches from a shopping list, a search with its options in every line, e.g.
+//! # Weekly restock
+//! butter gloss --brand nyx --max-results 5
+//! colorete --websites maquillalia --brand "Estée Lauder"
+
+use clap::Parser;
+use scrapped_webs::locale::{Label, Locale};
+
+use crate::parameters::{Args, SearchRequest};
+use crate::scraper_handler::{ResultsByWebsite, StatisticsByWebsite};
+use crate::summary::Summary;
+
+/// Start of the comments, the lines starting with it are not searched.
+const COMMENT: char = '#';
+
+/// A search of a batch and its results.
+#[derive(Debug)]
+pub struct Search {
+    /// The product searched.
+    pub product: String,
+    /// The products found for every website.
+    pub results_by_website: ResultsByWebsite,
+    /// The products found and the time spent in every website.
+    pub statistics_by_website: StatisticsByWebsite,
+    /// The statistics of the search.
+    pub summary: Summary,
+}
+
+/// Returns the searches of a batch, skipping the empty lines and the comments.
+/// # Arguments
+/// * `contents` - The lines of the batch.
+/// # Returns
+/// The arguments of every search, or an error naming every line that is not valid.
+pub fn parse(contents: &str) -> Result<Vec<Args>, anyhow::Error> {
+    let mut searches: Vec<Args> = Vec::new();
+    let mut errors: Vec<String> = Vec::new();
+    for (number, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with(COMMENT) {
+            continue;
+        }
+        match parse_line(line) {
+            Ok(args) => searches.push(args),
+            // Only the first line of the errors of the options, without the usage.
+            Err(err) => errors.push(format!(
+                "line {}: {}",
+                number + 1,
+                err.to_string().lines().next().unwrap_or_default()
+            )),
+        }
+    }
+    match errors.is_empty() {
+        true => Ok(searches),
+        false => Err(anyhow::anyhow!(errors.join("\n"))),
+    }
+}
+
+/// Returns the search of a line: the product followed by the options of the search subcommand.
+/// # Example
+/// butter gloss --brand nyx --max-results 5
+fn parse_line(line: &str) -> Result<Args, anyhow::Error> {
+    // The product is the text before the first option, with its quotes kept for the query syntax.
+    let (product, options) = match line.starts_with('-') {
+        true => ("", line),
+        false => line.split_at(line.find(" --").map_or(line.len(), |index| index + 1)),
+    };
+    let mut arguments = vec![String::from("batch")];
+    if !product.trim().is_empty() {
+        arguments.extend([String::from("--product"), product.trim().to_string()]);
+    }
+    arguments.extend(split_arguments(options)?);
+    let mut args = SearchRequest::try_parse_from(arguments)?.args;
+    args.tui = false;
+    Ok(args)
+}
+
+/// Splits the options of a line in arguments by the whitespace, keeping together the text between double quotes.
+fn split_arguments(text: &str) -> Result<Vec<String>, anyhow::Error> {
+    let mut arguments: Vec<String> = Vec::new();
+    let mut argument: Option<String> = None;
+    let mut quoted = false;
+    for character in text.chars() {
+        match character {
+            '"' => {
+                quoted = !quoted;
+                argument.get_or_insert_with(String::new);
+            }
+            character if character.is_whitespace() && !quoted => {
+                arguments.extend(argument.take());
+            }
+            character => argument.get_or_insert_with(String::new).push(character),
+        }
+    }
+    if quoted {
+        return Err(anyhow::anyhow!("the quotes are not closed"));
+    }
+    arguments.extend(argument);
+    Ok(arguments)
+}
+
+/// Returns the products and statistics of every website in all the searches together.
+pub fn combine(searches: &[Search]) -> (ResultsByWebsite, StatisticsByWebsite) {
+    let mut results_by_website = ResultsByWebsite::new();
+    let mut statistics_by_website = StatisticsByWebsite::new();
+    for search in searches {
+        for (website, products) in search.results_by_website.iter() {
+            results_by_website
+                .entry(website.clone())
+                .or_default()
+                .extend(products.iter().cloned());
+        }
+        for (website, search_statistics) in search.statistics_by_website.iter() {
+            let combined = statistics_by_website.entry(website.clone()).or_default();
+            combined.found += search_statistics.found;
+            combined.filtered_by_similarity += search_statistics.filtered_by_similarity;
+            combined.elapsed += search_statistics.elapsed;
+        }
+    }
+    (results_by_website, statistics_by_website)
+}
+
+/// Returns a line for every search with the products found, filtered by similarity and shown, and the cheapest price.
+/// # Example
+/// butter gloss: 12 found, 4 filtered by similarity, 3 shown, from €5.00
+pub fn overview(searches: &[Search], locale: Locale) -> Vec<String> {
+    searches
+        .iter()
+        .map(|search| {
+            let overall = &search.summary.overall;
+            let mut line = format!(
+                "{}: {} {}, {} {}, {} {}",
+                search.product,
+                overall.found,
+                locale.label(Label::Found),
+                overall.filtered_by_similarity,
+                locale.label(Label::FilteredBySimilarity),
+                overall.shown,
+                locale.label(Label::Shown)
+            );
+            if let Some(cheapest) = overall.cheapest {
+                line.push_str(
+                    format!(", {} {}", locale.label(Label::From), locale.price(cheapest)).as_str(),
+                );
+            }
+            line
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use scrapped_webs::product::Product;
+
+    use super::*;
+    use crate::parameters::Website;
+    use crate::scraper_handler::SearchStatistics;
+
+    /// Tests the searches and options of every line, skipping the comments and empty lines.
+    #[test]
+    fn parse_lines() {
+        let searches = parse(
+            "# Weekly restock\n\
+             butter gloss --brand nyx --max-results 5\n\
+             \n\
+             \"kind words\" -gloss --websites maquillalia --brand \"Estée Lauder\"\n\
+             --product colorete --sort-by price\n",
+        )
+        .unwrap();
+        assert_eq!(searches.len(), 3);
+        assert_eq!(searches[0].product, "butter gloss");
+        assert_eq!(searches[0].filters.brands, vec!["nyx"]);
+        assert_eq!(searches[0].max_results, Some(5));
+        assert_eq!(searches[1].product, "\"kind words\" -gloss");
+        assert_eq!(searches[1].websites, vec![Website::Maquillalia]);
+        assert_eq!(searches[1].filters.brands, vec!["Estée Lauder"]);
+        assert_eq!(searches[2].product, "colorete");
+        assert!(searches[2].sort_by.is_some());
+    }
+
+    /// Tests every line that is not valid is reported.
+    #[test]
+    fn parse_errors() {
+        let err = parse("labial --max-results many\nlabial\ncolorete --brand \"nyx\n--tui")
+            .unwrap_err()
+            .to_string();
+        assert!(err.starts_with("line 1: error: Invalid value \"many\""));
+        assert!(err.contains("line 3: the quotes are not closed"));
+        assert!(err.contains("line 4: error:"));
+        assert!(parse("# Nothing to search\n\n").unwrap().is_empty());
+    }
+
+    /// Tests the products and statistics of the searches are added for every website.
+    #[test]
+    fn combine_searches() {
+        let product = |name: &str| Product {
+            name: String::from(name),
+            ..Default::default()
+        };
+        let statistics = |found: usize| SearchStatistics {
+            found,
+            filtered_by_similarity: found / 2,
+            elapsed: Duration::from_secs(1),
+        };
+        let search = |results_by_website, statistics_by_website| Search {
+            product: String::from("labial"),
+            results_by_website,
+            statistics_by_website,
+            summary: Summary::default(),
+        };
+        let (results_by_website, statistics_by_website) = combine(&[
+            search(
+                ResultsByWebsite::from([(Website::Maquillalia, vec![product("Gloss")])]),
+                StatisticsByWebsite::from([(Website::Maquillalia, statistics(4))]),
+            ),
+            search(
+                ResultsByWebsite::from([
+                    (Website::Maquillalia, vec![product("Blush")]),
+                    (Website::SephoraSpain, Vec::new()),
+                ]),
+                StatisticsByWebsite::from([
+                    (Website::Maquillalia, statistics(2)),
+                    (Website::SephoraSpain, statistics(0)),
+                ]),
+            ),
+        ]);
+        assert_eq!(results_by_website[&Website::Maquillalia].len(), 2);
+        assert!(results_by_website[&Website::SephoraSpain].is_empty());
+        assert_eq!(statistics_by_website[&Website::Maquillalia].found, 6);
+        assert_eq!(
+            statistics_by_website[&Website::Maquillalia].filtered_by_similarity,
+            3
+        );
+        assert_eq!(
+            statistics_by_website[&Website::Maquillalia].elapsed,
+            Duration::from_secs(2)
+        );
+    }
+}
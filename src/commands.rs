@@ -0,0 +1,525 @@
+//! The subcommands of the command line.
+
+use std::collections::HashMap;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use ansi_term::Style;
+use clap::ArgEnum;
+use scrapped_webs::colour::ColourMode;
+use scrapped_webs::locale::Locale;
+use scrapped_webs::product::MISSING_FIELD;
+
+use crate::batch::{self, Search};
+use crate::config::{Config, Settings};
+use crate::history::{self, Entry};
+use crate::parameters::{Args, Cli, Command, ConfigCommand, OutputArgs, OutputFormat, Website};
+use crate::parameters_processor::ParametersProcessor;
+use crate::scraper_handler::{ResultsByWebsite, ScraperHandler};
+use crate::summary::Summary;
+use crate::terminal_visualizer::{self, Offer};
+use crate::{completions, exporter, html_visualizer, man, markdown_visualizer, server, tui};
+
+/// Runs the subcommand of the command line.
+/// The configuration is only read by the subcommands that use it, so the others work without it.
+pub fn run(cli: Cli) {
+    let config = || match Config::load(cli.profile.as_deref()) {
+        Ok(config) => config,
+        Err(err) => {
+            eprintln!("Error reading the configuration: {:?}", err);
+            std::process::exit(1);
+        }
+    };
+    match cli.command {
+        Command::Search(args) => search(args, cli.output, &config()),
+        Command::Compare(args) => compare(args, cli.output, &config()),
+        Command::Lookup { url } => lookup(&url, cli.output, &config()),
+        Command::Sites => sites(),
+        Command::History { limit } => show_history(limit, cli.output),
+        Command::Watch {
+            args,
+            interval,
+            times,
+        } => watch(args, cli.output, &config(), interval, times),
+        Command::Batch { file } => run_batch(file.as_deref(), cli.output, &config()),
+        Command::Config {
+            command: ConfigCommand::Show,
+        } => show_config(&config()),
+        Command::Serve { address } => {
+            if let Err(err) = server::serve(&address, &config()) {
+                eprintln!("Error serving in {address}: {:?}", err);
+                std::process::exit(1);
+            }
+        }
+        Command::Completions { shell } => write(
+            &completions::generate(shell),
+            cli.output.output_file.as_ref(),
+        ),
+        Command::Man => write(&man::generate(), cli.output.output_file.as_ref()),
+    }
+}
+
+/// Searches the product in the websites, saves the search in the history and shows the results.
+fn search(args: Args, output: OutputArgs, config: &Config) {
+    let mut scraper_handler = ScraperHandler::new(ParametersProcessor::new(args, output, config));
+    let results_by_website = scraper_handler.get_results();
+    let parameters_processor = scraper_handler.parameters_processor();
+    let summary = Summary::new(&results_by_website, scraper_handler.statistics_by_website());
+    record_history(parameters_processor.product(), &summary);
+
+    if parameters_processor.tui() {
+        if let Err(err) = tui::run(
+            &results_by_website,
+            parameters_processor.sort_by(),
+            parameters_processor.locale(),
+        ) {
+            eprintln!("Error running the interactive UI: {:?}", err);
+            std::process::exit(1);
+        }
+        return;
+    }
+    write_output(&results_by_website, &summary, parameters_processor);
+}
+
+/// Searches the product and shows the same product of every website side by side, unless other format is requested.
+fn compare(args: Args, mut output: OutputArgs, config: &Config) {
+    output.output_format.get_or_insert(OutputFormat::Table);
+    search(args, output, config);
+}
+
+/// Shows the product of a url of a supported website.
+fn lookup(url: &str, output: OutputArgs, config: &Config) {
+    let mut scraper_handler =
+        ScraperHandler::new(ParametersProcessor::new(Args::look_up(url), output, config));
+    let results_by_website = match scraper_handler.look_up(url) {
+        Ok(results_by_website) => results_by_website,
+        Err(err) => {
+            eprintln!("Error looking up {url}: {:?}", err);
+            std::process::exit(1);
+        }
+    };
+    let summary = Summary::new(&results_by_website, scraper_handler.statistics_by_website());
+    write_output(
+        &results_by_website,
+        &summary,
+        scraper_handler.parameters_processor(),
+    );
+}
+
+/// Prints the supported websites, with the value to use in --websites.
+fn sites() {
+    for website in Website::value_variants() {
+        if *website != Website::All {
+            let name = website.to_possible_value().unwrap().get_name().to_string();
+            println!("{name:<15} {website}");
+        }
+    }
+}
+
+/// Prints the most recent searches of the history.
+fn show_history(limit: usize, output: OutputArgs) {
+    let locale = output.locale.unwrap_or_else(Locale::detect);
+    let entries = match history::path().map(|path| history::load(&path)) {
+        Some(Ok(entries)) => entries,
+        Some(Err(err)) => {
+            eprintln!("Error reading the history: {:?}", err);
+            std::process::exit(1);
+        }
+        None => Vec::new(),
+    };
+    if entries.is_empty() {
+        println!("No searches yet.");
+    }
+    for entry in entries.iter().skip(entries.len().saturating_sub(limit)) {
+        println!("{}", entry.terminal_format(locale));
+    }
+}
+
+/// Prints the configuration file, the profile and the settings of the searches with their origin.
+fn show_config(config: &Config) {
+    match config.path() {
+        Some(path) if path.exists() => println!("Configuration file: {}", path.display()),
+        Some(path) => println!("Configuration file: {} (not found)", path.display()),
+        None => println!("Configuration file: none"),
+    }
+    println!(
+        "Profile: {}",
+        config.profile().map_or("none", |profile| profile.as_str())
+    );
+    print!("{}", config.resolve(&Settings::default()).terminal_format());
+}
+
+/// Repeats the search periodically, showing all the results the first time and the price changes after it.
+/// # Arguments
+/// * `args` - The search to repeat.
+/// * `output` - The output of the first results.
+/// * `config` - The configuration of the search.
+/// * `interval` - Seconds between searches.
+/// * `times` - Number of searches before exiting, forever if None.
+fn watch(args: Args, output: OutputArgs, config: &Config, interval: u64, times: Option<usize>) {
+    let mut scraper_handler = ScraperHandler::new(ParametersProcessor::new(args, output, config));
+    let mut previous_prices: Option<Prices> = None;
+    let mut searches: usize = 0;
+    loop {
+        let results_by_website = scraper_handler.get_results();
+        let parameters_processor = scraper_handler.parameters_processor();
+        match previous_prices.as_ref() {
+            None => {
+                let summary =
+                    Summary::new(&results_by_website, scraper_handler.statistics_by_website());
+                write_output(&results_by_website, &summary, parameters_processor);
+            }
+            Some(previous_prices) => {
+                let now = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map_or(0, |duration| duration.as_secs());
+                let changes = price_changes(
+                    previous_prices,
+                    &results_by_website,
+                    parameters_processor.locale(),
+                );
+                match changes.is_empty() {
+                    true => println!("{} UTC - No changes.", history::date_time(now)),
+                    false => println!("{} UTC\n{}", history::date_time(now), changes.join("\n")),
+                }
+            }
+        }
+        previous_prices = Some(prices(&results_by_website));
+
+        searches += 1;
+        if times.is_some_and(|times| searches >= times) {
+            break;
+        }
+        thread::sleep(Duration::from_secs(interval));
+    }
+}
+
+/// Searches every product of a batch, saving them in the history, and shows the results of every search
+/// followed by a line for every search and the summary of all of them.
+/// # Arguments
+/// * `file` - The file with the searches, the standard input if it is None or -.
+/// * `output` - The output of every search and of the summary.
+/// * `config` - The configuration of the searches, for the options not in the lines.
+fn run_batch(file: Option<&Path>, output: OutputArgs, config: &Config) {
+    let output_format = output
+        .output_format
+        .clone()
+        .unwrap_or(OutputFormat::Terminal);
+    if matches!(output_format, OutputFormat::Html | OutputFormat::Csv) {
+        eprintln!("The searches of a batch can not be shown as {output_format}, use terminal, table, markdown, json or yaml.");
+        std::process::exit(1);
+    }
+    let contents = match file.filter(|file| *file != Path::new("-")) {
+        Some(file) => std::fs::read_to_string(file),
+        None => io::read_to_string(io::stdin()),
+    };
+    let all_args = match contents
+        .map_err(anyhow::Error::from)
+        .and_then(|contents| batch::parse(&contents))
+    {
+        Ok(all_args) => all_args,
+        Err(err) => {
+            eprintln!("Error reading the searches of the batch:\n{err}");
+            std::process::exit(1);
+        }
+    };
+    if all_args.is_empty() {
+        eprintln!("There are no searches in the batch.");
+        return;
+    }
+
+    let mut searches: Vec<Search> = Vec::new();
+    let mut out = String::new();
+    let (mut colour_mode, mut locale) = (ColourMode::Plain, Locale::EnGb);
+    for args in all_args {
+        let mut scraper_handler =
+            ScraperHandler::new(ParametersProcessor::new(args, output.clone(), config));
+        let results_by_website = scraper_handler.get_results();
+        let parameters_processor = scraper_handler.parameters_processor();
+        let summary = Summary::new(&results_by_website, scraper_handler.statistics_by_website());
+        let product = parameters_processor.product().to_string();
+        record_history(&product, &summary);
+        (colour_mode, locale) = (
+            parameters_processor.colour_mode(),
+            parameters_processor.locale(),
+        );
+
+        // The machine-readable formats are a single document with every search.
+        if !matches!(output_format, OutputFormat::Json | OutputFormat::Yaml) {
+            out.push_str(&batch_heading(&product, &output_format, colour_mode));
+            match render(&results_by_website, &summary, parameters_processor) {
+                Ok(rendered) => out.push_str(&rendered),
+                Err(err) => {
+                    eprintln!("Error exporting the results: {:?}", err);
+                    std::process::exit(1);
+                }
+            }
+        }
+        searches.push(Search {
+            product,
+            results_by_website,
+            statistics_by_website: scraper_handler.statistics_by_website().clone(),
+            summary,
+        });
+    }
+
+    let (results_by_website, statistics_by_website) = batch::combine(&searches);
+    let summary = Summary::new(&results_by_website, &statistics_by_website);
+    let overview = batch::overview(&searches, locale);
+    match output_format {
+        OutputFormat::Json | OutputFormat::Yaml => {
+            match exporter::export_batch(&searches, &summary, &output_format) {
+                Ok(export) => out.push_str(&export),
+                Err(err) => {
+                    eprintln!("Error exporting the results: {:?}", err);
+                    std::process::exit(1);
+                }
+            }
+        }
+        OutputFormat::Markdown => {
+            out.push_str("# All the searches\n\n");
+            for line in overview {
+                out.push_str(format!("- {line}\n").as_str());
+            }
+            out.push('\n');
+            out.push_str(&markdown_visualizer::summary_table(&summary, locale));
+        }
+        _ => {
+            out.push_str(&batch_heading(
+                "All the searches",
+                &output_format,
+                colour_mode,
+            ));
+            for line in overview {
+                out.push_str(format!("{line}\n").as_str());
+            }
+            out.push_str(&terminal_visualizer::format_summary(
+                &summary,
+                colour_mode,
+                locale,
+            ));
+        }
+    }
+    write(&out, output.output_file.as_ref());
+}
+
+/// Returns the heading of a search of a batch, or of the summary of all of them.
+fn batch_heading(title: &str, output_format: &OutputFormat, colour_mode: ColourMode) -> String {
+    match (output_format, colour_mode) {
+        (OutputFormat::Markdown, _) => format!("# {title}\n\n"),
+        (_, ColourMode::Plain) => format!("\n=== {title} ===\n"),
+        _ => format!("\n{}\n", Style::new().bold().underline().paint(title)),
+    }
+}
+
+/// Name and price of every product found, by link.
+type Prices = HashMap<String, (String, Option<f32>)>;
+
+/// Returns the name and price of every product found, by link.
+fn prices(results_by_website: &ResultsByWebsite) -> Prices {
+    results_by_website
+        .values()
+        .flatten()
+        .map(|product| {
+            (
+                product.link.clone(),
+                (product.full_name(), Offer::new(product).price()),
+            )
+        })
+        .collect()
+}
+
+/// Returns the products new, gone or with a different price since the previous search, sorted.
+fn price_changes(
+    previous_prices: &Prices,
+    results_by_website: &ResultsByWebsite,
+    locale: Locale,
+) -> Vec<String> {
+    let price =
+        |price: Option<f32>| price.map_or(String::from(MISSING_FIELD), |price| locale.price(price));
+    let current_prices = prices(results_by_website);
+    let mut changes: Vec<String> = Vec::new();
+    for (link, (name, current_price)) in current_prices.iter() {
+        match previous_prices.get(link) {
+            None => changes.push(format!("New: {name} - {}", price(*current_price))),
+            Some((_, previous_price)) if previous_price != current_price => changes.push(format!(
+                "{name}: {} -> {}",
+                price(*previous_price),
+                price(*current_price)
+            )),
+            Some(_) => {}
+        }
+    }
+    for (link, (name, previous_price)) in previous_prices.iter() {
+        if !current_prices.contains_key(link) {
+            changes.push(format!("Gone: {name} - {}", price(*previous_price)));
+        }
+    }
+    changes.sort();
+    changes
+}
+
+/// Saves the search in the history, only reporting if it could not be saved.
+fn record_history(product: &str, summary: &Summary) {
+    if let Some(path) = history::path() {
+        if let Err(err) = history::record(&path, &Entry::new(product, summary)) {
+            eprintln!("Error saving the search in {}: {:?}", path.display(), err);
+        }
+    }
+}
+
+/// Returns the results in the output format requested.
+/// # Arguments
+/// * `results_by_website` - The products found for every website.
+/// * `summary` - The statistics of the search.
+/// * `parameters_processor` - The output format and how to print it.
+/// # Returns
+/// The results formatted, or an error if they could not be exported.
+pub fn render(
+    results_by_website: &ResultsByWebsite,
+    summary: &Summary,
+    parameters_processor: &ParametersProcessor,
+) -> Result<String, anyhow::Error> {
+    let colour_mode = parameters_processor.colour_mode();
+    let locale = parameters_processor.locale();
+    let output = match parameters_processor.output_format() {
+        OutputFormat::Terminal => {
+            terminal_visualizer::format(
+                results_by_website,
+                parameters_processor.sort_by(),
+                colour_mode,
+                locale,
+                parameters_processor.show_unavailable(),
+            ) + &terminal_visualizer::format_summary(summary, colour_mode, locale)
+        }
+        OutputFormat::Table => {
+            terminal_visualizer::format_table(
+                results_by_website,
+                terminal_visualizer::terminal_width(),
+                colour_mode,
+                locale,
+            ) + &terminal_visualizer::format_summary(summary, colour_mode, locale)
+        }
+        OutputFormat::Html => html_visualizer::format(
+            results_by_website,
+            summary,
+            parameters_processor.product(),
+            parameters_processor.sort_by().primary(),
+            locale,
+        ),
+        OutputFormat::Markdown => markdown_visualizer::format(results_by_website, summary, locale),
+        output_format => exporter::export(results_by_website, summary, output_format)?,
+    };
+    Ok(output)
+}
+
+/// Writes the results in the output file or the standard output, exiting if it is not possible.
+fn write_output(
+    results_by_website: &ResultsByWebsite,
+    summary: &Summary,
+    parameters_processor: &ParametersProcessor,
+) {
+    let output = match render(results_by_website, summary, parameters_processor) {
+        Ok(output) => output,
+        Err(err) => {
+            eprintln!("Error exporting the results: {:?}", err);
+            std::process::exit(1);
+        }
+    };
+    write(&output, parameters_processor.output_file());
+}
+
+/// Writes the output in the output file or the standard output, exiting if it is not possible.
+fn write(output: &str, output_file: Option<&PathBuf>) {
+    match output_file {
+        Some(output_file) => {
+            if let Err(err) = std::fs::write(output_file, output) {
+                eprintln!("Error writing {}: {:?}", output_file.display(), err);
+                std::process::exit(1);
+            }
+        }
+        None => print!("{output}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use scrapped_webs::product::Product;
+
+    use super::*;
+
+    /// Creates the results of a search with a product.
+    fn tear_up(link: &str, price: Option<f32>) -> ResultsByWebsite {
+        let product = Product::new(
+            String::from("Butter Gloss"),
+            Some(String::from("NYX")),
+            String::from(link),
+            price,
+            None,
+            None,
+            None,
+            0.9,
+            true,
+        );
+        ResultsByWebsite::from([(Website::Maquillalia, vec![product])])
+    }
+
+    /// Tests the changes between two searches.
+    #[test]
+    fn price_changes_all_cases() {
+        let previous_prices = prices(&tear_up("https://www.maquillalia.com/1", Some(8.99)));
+        assert!(price_changes(
+            &previous_prices,
+            &tear_up("https://www.maquillalia.com/1", Some(8.99)),
+            Locale::EnGb
+        )
+        .is_empty());
+        assert_eq!(
+            price_changes(
+                &previous_prices,
+                &tear_up("https://www.maquillalia.com/1", Some(7.5)),
+                Locale::EnGb
+            ),
+            vec!["NYX Butter Gloss: €8.99 -> €7.50"]
+        );
+        assert_eq!(
+            price_changes(
+                &previous_prices,
+                &tear_up("https://www.maquillalia.com/2", None),
+                Locale::EsEs
+            ),
+            vec![
+                "Gone: NYX Butter Gloss - 8,99 €",
+                "New: NYX Butter Gloss - N/A"
+            ]
+        );
+    }
+
+    /// Tests the results are rendered in the format requested.
+    #[test]
+    fn render_output_formats() {
+        let results_by_website = tear_up("https://www.maquillalia.com/1", Some(8.99));
+        let summary = Summary::default();
+        let args = Args::look_up("gloss");
+        let output = OutputArgs {
+            output_format: Some(OutputFormat::Json),
+            ..Default::default()
+        };
+        let parameters_processor =
+            ParametersProcessor::new(args.clone(), output, &Config::default());
+        let json = render(&results_by_website, &summary, &parameters_processor).unwrap();
+        assert!(json.starts_with("{\n  \"results\""));
+
+        let output = OutputArgs {
+            plain: true,
+            locale: Some(Locale::EnGb),
+            ..Default::default()
+        };
+        let parameters_processor = ParametersProcessor::new(args, output, &Config::default());
+        let terminal = render(&results_by_website, &summary, &parameters_processor).unwrap();
+        assert!(terminal.contains("NYX - €8.99"));
+        assert!(terminal.contains("Summary"));
+    }
+}
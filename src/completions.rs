@@ -0,0 +1,62 @@
+//! Shell completions of the command line, generated from its definition
+//! so every new subcommand, option or value of the enums is completed.
+
+use crate::parameters::{Cli, Shell};
+
+impl From<Shell> for clap_complete::Shell {
+    fn from(shell: Shell) -> Self {
+        match shell {
+            Shell::Bash => clap_complete::Shell::Bash,
+            Shell::Zsh => clap_complete::Shell::Zsh,
+            Shell::Fish => clap_complete::Shell::Fish,
+            Shell::Powershell => clap_complete::Shell::PowerShell,
+        }
+    }
+}
+
+/// Returns the completions of the command line for a shell.
+/// # Arguments
+/// * `shell` - The shell to complete.
+/// # Returns
+/// The script to source in the shell, e.g. in ~/.bashrc or /etc/bash_completion.d/makeup-comparator.
+pub fn generate(shell: Shell) -> String {
+    let mut command = Cli::command();
+    let name = command.get_name().to_string();
+    let mut script = Vec::new();
+    clap_complete::generate(
+        clap_complete::Shell::from(shell),
+        &mut command,
+        name,
+        &mut script,
+    );
+    String::from_utf8_lossy(&script).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::ValueEnum;
+
+    use super::*;
+
+    /// Tests the completions of every shell have the subcommands and the options,
+    /// and the ones of bash, zsh and fish the values of the enums and every sorting criterion.
+    #[test]
+    fn generate_shells() {
+        for shell in Shell::value_variants() {
+            let script = generate(*shell);
+            for expected in ["search", "completions", "websites", "sort-by"] {
+                assert!(script.contains(expected), "{shell:?} without {expected}");
+            }
+        }
+        for shell in [Shell::Bash, Shell::Zsh, Shell::Fish] {
+            let script = generate(shell);
+            for expected in ["sephora-spain", "maquillalia", "similarity:asc", "rating"] {
+                assert!(script.contains(expected), "{shell:?} without {expected}");
+            }
+        }
+        let bash = generate(Shell::Bash);
+        assert!(bash.contains("complete -F _makeup-comparator"));
+        assert!(bash.contains("powershell"));
+        assert!(generate(Shell::Zsh).starts_with("#compdef makeup-comparator\n"));
+    }
+}
@@ -0,0 +1,575 @@
+//! Configuration file with default values and named profiles, overridden by the environment and the command line.
+//!
+//! The file is `$XDG_CONFIG_HOME/makeup-comparator/config.toml` (`~/.config` if it is not defined), e.g.
+//! [defaults]
+//! websites = ["all"]
+//! max-results = 20
+//!
+//! [profiles.quick]
+//! websites = ["maquillalia"]
+//! max-results = 5
+//! max-results-per-site = 5
+
+use std::collections::BTreeMap;
+use std::fmt::{Display, Error, Formatter};
+use std::fs;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use clap::ArgEnum;
+use serde::Deserialize;
+
+use crate::parameters::{Args, SortBy, SortingType, Website};
+
+/// Name of the configuration file inside the configuration directory.
+const CONFIG_FILE: &str = "makeup-comparator/config.toml";
+/// Variable with the path of the configuration file, to use other file.
+const CONFIG_VARIABLE: &str = "MAKEUP_COMPARATOR_CONFIG";
+/// Variable with the profile used when --profile is not provided.
+const PROFILE_VARIABLE: &str = "MAKEUP_COMPARATOR_PROFILE";
+/// Variables overriding the configuration file.
+const WEBSITES_VARIABLE: &str = "MAKEUP_COMPARATOR_WEBSITES";
+const MAX_RESULTS_VARIABLE: &str = "MAKEUP_COMPARATOR_MAX_RESULTS";
+const MAX_RESULTS_PER_SITE_VARIABLE: &str = "MAKEUP_COMPARATOR_MAX_RESULTS_PER_SITE";
+const MIN_SIMILARITY_VARIABLE: &str = "MAKEUP_COMPARATOR_MIN_SIMILARITY";
+const SORT_BY_VARIABLE: &str = "MAKEUP_COMPARATOR_SORT_BY";
+const BRANDS_VARIABLE: &str = "MAKEUP_COMPARATOR_BRANDS";
+const EXCLUDE_BRANDS_VARIABLE: &str = "MAKEUP_COMPARATOR_EXCLUDE_BRANDS";
+
+/// Values used when they are not set anywhere.
+const DEFAULT_MAX_RESULTS_PER_SITE: usize = 50;
+const DEFAULT_MIN_SIMILARITY: f32 = 0.0;
+const DEFAULT_SORTING_TYPE: SortingType = SortingType::Similarity;
+
+/// Search settings that can be configured, None if they are not set.
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct Settings {
+    pub websites: Option<Vec<Website>>,
+    pub max_results: Option<usize>,
+    pub max_results_per_site: Option<usize>,
+    pub min_similarity: Option<f32>,
+    pub sort_by: Option<SortBy>,
+    pub brands: Option<Vec<String>>,
+    pub exclude_brands: Option<Vec<String>>,
+}
+
+impl Settings {
+    /// Returns the settings given in the command line.
+    pub fn from_args(args: &Args) -> Self {
+        Self {
+            websites: (!args.websites.is_empty()).then(|| args.websites.clone()),
+            max_results: args.max_results,
+            max_results_per_site: args.max_results_per_site,
+            min_similarity: args.min_similarity,
+            sort_by: args.sort_by.clone(),
+            brands: (!args.filters.brands.is_empty()).then(|| args.filters.brands.clone()),
+            exclude_brands: (!args.filters.exclude_brands.is_empty())
+                .then(|| args.filters.exclude_brands.clone()),
+        }
+    }
+
+    /// Returns the settings of the MAKEUP_COMPARATOR_* variables.
+    /// # Arguments
+    /// * `variable` - Returns the value of a variable, None if it is not defined.
+    /// # Returns
+    /// The settings, or an error naming the variable with a value not valid.
+    /// # Example
+    /// MAKEUP_COMPARATOR_WEBSITES=sephora-spain,maquillalia MAKEUP_COMPARATOR_SORT_BY=price
+    fn from_environment(variable: impl Fn(&str) -> Option<String>) -> Result<Self, anyhow::Error> {
+        let parse = |name: &str| -> Result<Option<String>, anyhow::Error> {
+            Ok(variable(name).filter(|value| !value.trim().is_empty()))
+        };
+        let invalid = |name: &str, value: &str| anyhow::anyhow!("{name}={value} is not valid");
+        let list = |value: String| {
+            value
+                .split(',')
+                .map(|item| item.trim().to_string())
+                .filter(|item| !item.is_empty())
+                .collect::<Vec<String>>()
+        };
+        Ok(Self {
+            websites: parse(WEBSITES_VARIABLE)?
+                .map(|value| {
+                    value
+                        .split(',')
+                        .map(|website| Website::from_str(website.trim(), true))
+                        .collect::<Result<Vec<Website>, String>>()
+                        .map_err(|_| invalid(WEBSITES_VARIABLE, &value))
+                })
+                .transpose()?,
+            max_results: parse(MAX_RESULTS_VARIABLE)?
+                .map(|value| {
+                    usize::from_str(value.trim()).map_err(|_| invalid(MAX_RESULTS_VARIABLE, &value))
+                })
+                .transpose()?,
+            max_results_per_site: parse(MAX_RESULTS_PER_SITE_VARIABLE)?
+                .map(|value| {
+                    usize::from_str(value.trim())
+                        .map_err(|_| invalid(MAX_RESULTS_PER_SITE_VARIABLE, &value))
+                })
+                .transpose()?,
+            min_similarity: parse(MIN_SIMILARITY_VARIABLE)?
+                .map(|value| {
+                    f32::from_str(value.trim())
+                        .map_err(|_| invalid(MIN_SIMILARITY_VARIABLE, &value))
+                })
+                .transpose()?,
+            sort_by: parse(SORT_BY_VARIABLE)?
+                .map(|value| {
+                    SortBy::from_str(&value).map_err(|_| invalid(SORT_BY_VARIABLE, &value))
+                })
+                .transpose()?,
+            brands: parse(BRANDS_VARIABLE)?.map(list),
+            exclude_brands: parse(EXCLUDE_BRANDS_VARIABLE)?.map(list),
+        })
+    }
+}
+
+/// Contents of the configuration file.
+#[derive(Debug, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+    /// Settings of every search.
+    #[serde(default)]
+    defaults: Settings,
+    /// Settings used with --profile, over the defaults.
+    #[serde(default)]
+    profiles: BTreeMap<String, Settings>,
+}
+
+/// Where the value of a setting comes from.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Origin {
+    /// The value of the program.
+    Default,
+    /// The defaults of the configuration file.
+    ConfigFile,
+    /// A profile of the configuration file.
+    Profile(String),
+    /// An environment variable.
+    Environment,
+    /// The command line arguments.
+    CommandLine,
+}
+
+impl Display for Origin {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        match self {
+            Origin::Default => write!(f, "default"),
+            Origin::ConfigFile => write!(f, "config file"),
+            Origin::Profile(profile) => write!(f, "profile {profile}"),
+            Origin::Environment => write!(f, "environment"),
+            Origin::CommandLine => write!(f, "command line"),
+        }
+    }
+}
+
+/// The value of a setting and where it comes from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Setting<T> {
+    pub value: T,
+    pub origin: Origin,
+}
+
+/// The settings used in a search.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EffectiveSettings {
+    pub websites: Setting<Vec<Website>>,
+    /// Maximum of results of all the websites together, None if there is no limit.
+    pub max_results: Setting<Option<usize>>,
+    pub max_results_per_site: Setting<usize>,
+    pub min_similarity: Setting<f32>,
+    pub sort_by: Setting<SortBy>,
+    pub brands: Setting<Vec<String>>,
+    pub exclude_brands: Setting<Vec<String>>,
+}
+
+impl EffectiveSettings {
+    /// Formats the settings and their origin to be printed in terminal.
+    /// # Example
+    /// max-results          = 5 (profile quick)
+    pub fn terminal_format(&self) -> String {
+        let websites: Vec<&str> = self
+            .websites
+            .value
+            .iter()
+            .filter_map(|website| website.to_possible_value())
+            .map(|website| website.get_name())
+            .collect();
+        let list = |items: &[String]| match items.is_empty() {
+            true => String::from("none"),
+            false => items.join(", "),
+        };
+        [
+            (
+                "websites",
+                match websites.is_empty() {
+                    true => String::from("none"),
+                    false => websites.join(", "),
+                },
+                &self.websites.origin,
+            ),
+            (
+                "max-results",
+                self.max_results
+                    .value
+                    .map_or(String::from("none"), |max_results| max_results.to_string()),
+                &self.max_results.origin,
+            ),
+            (
+                "max-results-per-site",
+                self.max_results_per_site.value.to_string(),
+                &self.max_results_per_site.origin,
+            ),
+            (
+                "min-similarity",
+                self.min_similarity.value.to_string(),
+                &self.min_similarity.origin,
+            ),
+            (
+                "sort-by",
+                self.sort_by.value.to_string(),
+                &self.sort_by.origin,
+            ),
+            ("brands", list(&self.brands.value), &self.brands.origin),
+            (
+                "exclude-brands",
+                list(&self.exclude_brands.value),
+                &self.exclude_brands.origin,
+            ),
+        ]
+        .iter()
+        .map(|(name, value, origin)| format!("{name:<20} = {value} ({origin})\n"))
+        .collect()
+    }
+}
+
+/// The configuration of the searches: the configuration file, the profile and the environment.
+#[derive(Debug, Default)]
+pub struct Config {
+    /// The configuration file, None if it is not possible to know where it is.
+    path: Option<PathBuf>,
+    /// The profile used.
+    profile: Option<String>,
+    /// The settings from the lowest to the highest priority.
+    layers: Vec<(Origin, Settings)>,
+}
+
+impl Config {
+    /// Loads the configuration file and the environment variables.
+    /// # Arguments
+    /// * `profile` - The profile requested in the command line, the one of MAKEUP_COMPARATOR_PROFILE if None.
+    /// # Returns
+    /// The configuration, or an error if the file, the profile or a variable is not valid.
+    pub fn load(profile: Option<&str>) -> Result<Self, anyhow::Error> {
+        let path = path();
+        let contents = match path.as_ref().filter(|path| path.exists()) {
+            Some(path) => Some(fs::read_to_string(path)?),
+            None => None,
+        };
+        let profile = profile
+            .map(String::from)
+            .or_else(|| std::env::var(PROFILE_VARIABLE).ok())
+            .filter(|profile| !profile.is_empty());
+        let mut config = Self::new(
+            contents.as_deref(),
+            profile,
+            Settings::from_environment(|name| std::env::var(name).ok())?,
+        )
+        .map_err(|err| match path.as_ref() {
+            Some(path) => anyhow::anyhow!("{}: {err}", path.display()),
+            None => err,
+        })?;
+        config.path = path;
+        Ok(config)
+    }
+
+    /// Creates the configuration.
+    /// # Arguments
+    /// * `contents` - The TOML of the configuration file, None if there is no file.
+    /// * `profile` - The profile to use.
+    /// * `environment` - The settings of the environment variables.
+    /// # Returns
+    /// The configuration, or an error if the TOML is not valid or the profile is not in it.
+    fn new(
+        contents: Option<&str>,
+        profile: Option<String>,
+        environment: Settings,
+    ) -> Result<Self, anyhow::Error> {
+        let mut file: ConfigFile = match contents {
+            Some(contents) => toml::from_str(contents)?,
+            None => ConfigFile::default(),
+        };
+        let mut layers = vec![(Origin::ConfigFile, file.defaults)];
+        if let Some(profile) = profile.as_ref() {
+            let settings = file.profiles.remove(profile).ok_or_else(|| {
+                let profiles: Vec<&String> = file.profiles.keys().collect();
+                anyhow::anyhow!("there is no profile {profile}, the profiles are {profiles:?}")
+            })?;
+            layers.push((Origin::Profile(profile.clone()), settings));
+        }
+        layers.push((Origin::Environment, environment));
+        Ok(Self {
+            path: None,
+            profile,
+            layers,
+        })
+    }
+
+    /// Returns the configuration file, None if it is not possible to know where it is.
+    pub fn path(&self) -> Option<&PathBuf> {
+        self.path.as_ref()
+    }
+
+    /// Returns the profile used.
+    pub fn profile(&self) -> Option<&String> {
+        self.profile.as_ref()
+    }
+
+    /// Returns the settings of a search, taking every one from the command line,
+    /// the environment, the profile, the configuration file or the default value, in this order.
+    /// # Arguments
+    /// * `command_line` - The settings given in the command line.
+    pub fn resolve(&self, command_line: &Settings) -> EffectiveSettings {
+        EffectiveSettings {
+            websites: self.setting(
+                command_line,
+                |settings| settings.websites.clone(),
+                Vec::new(),
+            ),
+            max_results: self.setting(
+                command_line,
+                |settings| settings.max_results.map(Some),
+                None,
+            ),
+            max_results_per_site: self.setting(
+                command_line,
+                |settings| settings.max_results_per_site,
+                DEFAULT_MAX_RESULTS_PER_SITE,
+            ),
+            min_similarity: self.setting(
+                command_line,
+                |settings| settings.min_similarity,
+                DEFAULT_MIN_SIMILARITY,
+            ),
+            sort_by: self.setting(
+                command_line,
+                |settings| settings.sort_by.clone(),
+                SortBy::from(DEFAULT_SORTING_TYPE),
+            ),
+            brands: self.setting(command_line, |settings| settings.brands.clone(), Vec::new()),
+            exclude_brands: self.setting(
+                command_line,
+                |settings| settings.exclude_brands.clone(),
+                Vec::new(),
+            ),
+        }
+    }
+
+    /// Returns the value of a setting from the layer with the highest priority that sets it.
+    fn setting<T>(
+        &self,
+        command_line: &Settings,
+        field: impl Fn(&Settings) -> Option<T>,
+        default: T,
+    ) -> Setting<T> {
+        std::iter::once((&Origin::CommandLine, command_line))
+            .chain(
+                self.layers
+                    .iter()
+                    .rev()
+                    .map(|(origin, settings)| (origin, settings)),
+            )
+            .find_map(|(origin, settings)| {
+                field(settings).map(|value| Setting {
+                    value,
+                    origin: origin.clone(),
+                })
+            })
+            .unwrap_or(Setting {
+                value: default,
+                origin: Origin::Default,
+            })
+    }
+}
+
+/// Returns the configuration file: MAKEUP_COMPARATOR_CONFIG, or the file in $XDG_CONFIG_HOME or ~/.config.
+/// None if none of them is defined.
+fn path() -> Option<PathBuf> {
+    if let Some(path) = std::env::var_os(CONFIG_VARIABLE).filter(|path| !path.is_empty()) {
+        return Some(PathBuf::from(path));
+    }
+    let config_directory = match std::env::var_os("XDG_CONFIG_HOME") {
+        Some(config_home) if !config_home.is_empty() => PathBuf::from(config_home),
+        _ => PathBuf::from(std::env::var_os("HOME")?).join(".config"),
+    };
+    Some(config_directory.join(CONFIG_FILE))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parameters::{SortKey, SortOrder};
+
+    const CONFIG: &str = r#"
+[defaults]
+websites = ["all"]
+max-results = 20
+sort-by = "price"
+
+[profiles.quick]
+websites = ["maquillalia"]
+max-results = 5
+max-results-per-site = 5
+brands = ["NYX", "Milani"]
+
+[profiles.deep]
+max-results = 200
+min-similarity = 0.0
+"#;
+
+    /// Tests every setting is taken from the layer with the highest priority.
+    #[test]
+    fn resolve_all_origins() {
+        let environment = Settings {
+            sort_by: Some(SortBy::from(SortingType::Rating)),
+            ..Default::default()
+        };
+        let config = Config::new(Some(CONFIG), Some(String::from("quick")), environment).unwrap();
+        let command_line = Settings {
+            max_results: Some(10),
+            exclude_brands: Some(vec![String::from("Dior")]),
+            ..Default::default()
+        };
+        let settings = config.resolve(&command_line);
+        assert_eq!(
+            settings.websites,
+            Setting {
+                value: vec![Website::Maquillalia],
+                origin: Origin::Profile(String::from("quick"))
+            }
+        );
+        assert_eq!(settings.max_results.value, Some(10));
+        assert_eq!(settings.max_results.origin, Origin::CommandLine);
+        assert_eq!(settings.max_results_per_site.value, 5);
+        assert_eq!(
+            settings.max_results_per_site.origin,
+            Origin::Profile(String::from("quick"))
+        );
+        assert_eq!(settings.min_similarity.value, 0.0);
+        assert_eq!(settings.min_similarity.origin, Origin::Default);
+        assert_eq!(settings.sort_by.value, SortBy::from(SortingType::Rating));
+        assert_eq!(settings.sort_by.origin, Origin::Environment);
+        assert_eq!(
+            settings.brands.value,
+            vec![String::from("NYX"), String::from("Milani")]
+        );
+        assert_eq!(settings.exclude_brands.value, vec![String::from("Dior")]);
+        assert_eq!(settings.exclude_brands.origin, Origin::CommandLine);
+
+        let config = Config::new(Some(CONFIG), None, Settings::default()).unwrap();
+        let settings = config.resolve(&Settings::default());
+        assert_eq!(settings.max_results.value, Some(20));
+        assert_eq!(settings.max_results.origin, Origin::ConfigFile);
+        assert_eq!(
+            settings.terminal_format(),
+            "websites             = all (config file)\n\
+             max-results          = 20 (config file)\n\
+             max-results-per-site = 50 (default)\n\
+             min-similarity       = 0 (default)\n\
+             sort-by              = price:asc (config file)\n\
+             brands               = none (default)\n\
+             exclude-brands       = none (default)\n"
+        );
+    }
+
+    /// Tests the configuration files and profiles that can not be used.
+    #[test]
+    fn new_errors() {
+        let err = Config::new(
+            Some(CONFIG),
+            Some(String::from("slow")),
+            Settings::default(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            r#"there is no profile slow, the profiles are ["deep", "quick"]"#
+        );
+        assert!(Config::new(
+            Some("[defaults]\nmax-result = 5"),
+            None,
+            Settings::default()
+        )
+        .is_err());
+        assert!(Config::new(None, Some(String::from("quick")), Settings::default()).is_err());
+        let settings = Config::new(None, None, Settings::default())
+            .unwrap()
+            .resolve(&Settings::default());
+        assert_eq!(
+            settings.max_results,
+            Setting {
+                value: None,
+                origin: Origin::Default
+            }
+        );
+        assert_eq!(
+            settings.max_results_per_site,
+            Setting {
+                value: DEFAULT_MAX_RESULTS_PER_SITE,
+                origin: Origin::Default
+            }
+        );
+    }
+
+    /// Tests the settings of the environment variables.
+    #[test]
+    fn from_environment_all_cases() {
+        let variables = |values: &'static [(&'static str, &'static str)]| {
+            move |name: &str| {
+                values
+                    .iter()
+                    .find(|(variable, _)| *variable == name)
+                    .map(|(_, value)| value.to_string())
+            }
+        };
+        assert_eq!(
+            Settings::from_environment(variables(&[
+                (WEBSITES_VARIABLE, "sephora-spain, Maquillalia"),
+                (MAX_RESULTS_VARIABLE, "15"),
+                (MAX_RESULTS_PER_SITE_VARIABLE, "8"),
+                (MIN_SIMILARITY_VARIABLE, "0.5"),
+                (SORT_BY_VARIABLE, "price:desc, rating"),
+                (BRANDS_VARIABLE, "nyx, L'Oréal Paris,"),
+            ]))
+            .unwrap(),
+            Settings {
+                websites: Some(vec![Website::SephoraSpain, Website::Maquillalia]),
+                max_results: Some(15),
+                max_results_per_site: Some(8),
+                min_similarity: Some(0.5),
+                sort_by: Some(SortBy(vec![
+                    SortKey {
+                        sorting_type: SortingType::Price,
+                        order: SortOrder::Desc
+                    },
+                    SortKey::new(SortingType::Rating)
+                ])),
+                brands: Some(vec![String::from("nyx"), String::from("L'Oréal Paris")]),
+                exclude_brands: None,
+            }
+        );
+        assert_eq!(
+            Settings::from_environment(variables(&[(MAX_RESULTS_VARIABLE, "")])).unwrap(),
+            Settings::default()
+        );
+        assert_eq!(
+            Settings::from_environment(variables(&[(MAX_RESULTS_VARIABLE, "many")]))
+                .unwrap_err()
+                .to_string(),
+            "MAKEUP_COMPARATOR_MAX_RESULTS=many is not valid"
+        );
+        assert!(Settings::from_environment(variables(&[(WEBSITES_VARIABLE, "amazon")])).is_err());
+    }
+}
@@ -0,0 +1,331 @@
+//! Export of the results to machine-readable formats.
+
+use std::collections::BTreeMap;
+
+use clap::ArgEnum;
+use scrapped_webs::product::Product;
+use serde::Serialize;
+
+use crate::{
+    batch::Search,
+    parameters::{OutputFormat, Website},
+    scraper_handler::ResultsByWebsite,
+    summary::{Statistics, Summary},
+};
+
+/// Document of the JSON and YAML exports.
+#[derive(Debug, Serialize)]
+struct Export<'a> {
+    /// The products grouped by website.
+    results: BTreeMap<&'a Website, &'a Vec<Product>>,
+    /// The statistics of the search.
+    summary: &'a Summary,
+}
+
+/// A search of the JSON and YAML exports of a batch.
+#[derive(Debug, Serialize)]
+struct BatchSearch<'a> {
+    /// The product searched.
+    product: &'a str,
+    /// The products grouped by website.
+    results: BTreeMap<&'a Website, &'a Vec<Product>>,
+    /// The statistics of the search.
+    summary: &'a Summary,
+}
+
+/// Document of the JSON and YAML exports of a batch.
+#[derive(Debug, Serialize)]
+struct BatchExport<'a> {
+    /// Every search of the batch.
+    searches: Vec<BatchSearch<'a>>,
+    /// The statistics of all the searches together.
+    summary: &'a Summary,
+}
+
+/// Row of the CSV export, one for every tone or one for the product if it has no tones.
+#[derive(Debug, Serialize, PartialEq)]
+struct CsvRow<'a> {
+    website: &'a Website,
+    brand: Option<&'a str>,
+    name: &'a str,
+    tone: Option<&'a str>,
+    price_standard: Option<f32>,
+    price_sales: Option<f32>,
+    discount_percentage: Option<u8>,
+    rating: Option<f32>,
+    available: bool,
+    similarity: f32,
+    link: &'a str,
+}
+
+/// Returns the results in the output format provided.
+/// # Arguments
+/// * `results_by_website` - The products found for every website.
+/// * `summary` - The statistics of the search, exported after the products except in CSV.
+/// * `output_format` - The machine-readable format to export.
+/// # Returns
+/// The exported results or an error if they could not be serialized.
+pub fn export(
+    results_by_website: &ResultsByWebsite,
+    summary: &Summary,
+    output_format: &OutputFormat,
+) -> Result<String, anyhow::Error> {
+    // Websites sorted to always export in the same order.
+    let results_by_website: BTreeMap<&Website, &Vec<Product>> = results_by_website.iter().collect();
+    let export = Export {
+        results: results_by_website.clone(),
+        summary,
+    };
+    match output_format {
+        OutputFormat::Json => Ok(serde_json::to_string_pretty(&export)? + "\n"),
+        OutputFormat::Yaml => Ok(serde_yaml::to_string(&export)?),
+        OutputFormat::Csv => to_csv(&results_by_website),
+        OutputFormat::Terminal
+        | OutputFormat::Table
+        | OutputFormat::Html
+        | OutputFormat::Markdown => Err(anyhow::anyhow!(
+            "{output_format} is not a machine-readable format"
+        )),
+    }
+}
+
+/// Returns the searches of a batch in the JSON or YAML format.
+/// # Arguments
+/// * `searches` - Every search of the batch and its results.
+/// * `summary` - The statistics of all the searches together, exported after them.
+/// * `output_format` - JSON or YAML.
+/// # Returns
+/// The exported searches or an error if they could not be serialized or the format is not supported.
+pub fn export_batch(
+    searches: &[Search],
+    summary: &Summary,
+    output_format: &OutputFormat,
+) -> Result<String, anyhow::Error> {
+    let export = BatchExport {
+        searches: searches
+            .iter()
+            .map(|search| BatchSearch {
+                product: &search.product,
+                // Websites sorted to always export in the same order.
+                results: search.results_by_website.iter().collect(),
+                summary: &search.summary,
+            })
+            .collect(),
+        summary,
+    };
+    match output_format {
+        OutputFormat::Json => Ok(serde_json::to_string_pretty(&export)? + "\n"),
+        OutputFormat::Yaml => Ok(serde_yaml::to_string(&export)?),
+        output_format => Err(anyhow::anyhow!(
+            "the searches of a batch can not be exported to {output_format}"
+        )),
+    }
+}
+
+/// Returns the results flattened to one row for every tone.
+fn to_csv(results_by_website: &BTreeMap<&Website, &Vec<Product>>) -> Result<String, anyhow::Error> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    for (website, products) in results_by_website {
+        for product in products.iter() {
+            for row in csv_rows(website, product) {
+                writer.serialize(row)?;
+            }
+        }
+    }
+    Ok(String::from_utf8(writer.into_inner()?)?)
+}
+
+/// Returns the rows for a product, one for every tone or one for the product if it has no tones.
+fn csv_rows<'a>(website: &'a Website, product: &'a Product) -> Vec<CsvRow<'a>> {
+    let product_row = CsvRow {
+        website,
+        brand: product.brand.as_deref().map(str::trim),
+        name: product.name.trim(),
+        tone: None,
+        price_standard: product.price_standard,
+        price_sales: product.price_sales,
+        discount_percentage: product.discount_percentage(),
+        rating: product.rating,
+        available: product.available,
+        similarity: product.similarity,
+        link: &product.link,
+    };
+    match product.tones.as_ref() {
+        Some(tones) => tones
+            .iter()
+            .map(|tone| CsvRow {
+                tone: tone.name.as_deref(),
+                price_standard: tone.price_standard,
+                price_sales: tone.price_sales,
+                discount_percentage: tone.discount_percentage(),
+                rating: tone.rating.or(product.rating),
+                available: tone.available,
+                link: tone.url.as_deref().unwrap_or(&product.link),
+                ..product_row
+            })
+            .collect(),
+        None => vec![product_row],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use scrapped_webs::product::Tone;
+
+    use super::*;
+    use crate::scraper_handler::StatisticsByWebsite;
+
+    /// Creates the results used in the tests.
+    fn tear_up() -> ResultsByWebsite {
+        let tone: Tone = Tone::new(
+            Some(String::from("Tone 1")),
+            Some(20.0),
+            Some(15.0),
+            false,
+            Some(String::from("http://www.test.com/tone-1")),
+            None,
+        );
+        let product_with_tones: Product = Product::new(
+            String::from("Product 1"),
+            Some(String::from("Brand")),
+            String::from("http://www.test.com"),
+            None,
+            None,
+            Some(vec![tone]),
+            Some(9.5),
+            0.95,
+            true,
+        );
+        let product_without_tones: Product = Product::new(
+            String::from("Product 2"),
+            None,
+            String::from("http://www.test2.com"),
+            Some(10.0),
+            None,
+            None,
+            None,
+            0.5,
+            true,
+        );
+        let mut results_by_website = ResultsByWebsite::new();
+        results_by_website.insert(Website::Maquillalia, vec![product_without_tones]);
+        results_by_website.insert(Website::SephoraSpain, vec![product_with_tones]);
+        results_by_website
+    }
+
+    /// Tests the JSON export keeps the products grouped by website, followed by the summary.
+    #[test]
+    fn export_json() {
+        let results_by_website = tear_up();
+        let summary = Summary::new(&results_by_website, &StatisticsByWebsite::new());
+        let output = export(&results_by_website, &summary, &OutputFormat::Json).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(json["summary"]["overall"]["shown"], 2);
+        assert_eq!(
+            json["summary"]["websites"]["sephora-spain"]["cheapest"],
+            15.0
+        );
+        assert_eq!(json["results"]["sephora-spain"][0]["name"], "Product 1");
+        assert_eq!(
+            json["results"]["sephora-spain"][0]["tones"][0]["name"],
+            "Tone 1"
+        );
+        assert_eq!(
+            json["results"]["sephora-spain"][0]["tones"][0]["price_sales"],
+            15.0
+        );
+        assert_eq!(
+            json["results"]["maquillalia"][0]["tones"],
+            serde_json::Value::Null
+        );
+        assert!(json["results"]["maquillalia"][0]
+            .get("similarity_explanation")
+            .is_none());
+    }
+
+    /// Tests the YAML export keeps the products grouped by website.
+    #[test]
+    fn export_yaml() {
+        let output = export(&tear_up(), &Summary::default(), &OutputFormat::Yaml).unwrap();
+        let yaml: serde_yaml::Value = serde_yaml::from_str(&output).unwrap();
+        assert_eq!(
+            yaml["results"]["sephora-spain"][0]["tones"][0]["name"],
+            "Tone 1"
+        );
+        assert_eq!(yaml["results"]["maquillalia"][0]["name"], "Product 2");
+        // Websites are sorted.
+        assert!(output.find("sephora-spain").unwrap() < output.find("maquillalia").unwrap());
+    }
+
+    /// Tests the JSON export of a batch has every search and the summary of all of them.
+    #[test]
+    fn export_batch_json() {
+        let results_by_website = tear_up();
+        let summary = Summary::new(&results_by_website, &StatisticsByWebsite::new());
+        let searches = vec![Search {
+            product: String::from("gloss"),
+            results_by_website,
+            statistics_by_website: StatisticsByWebsite::new(),
+            summary: Summary::default(),
+        }];
+        let output = export_batch(&searches, &summary, &OutputFormat::Json).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(json["searches"][0]["product"], "gloss");
+        assert_eq!(
+            json["searches"][0]["results"]["maquillalia"][0]["name"],
+            "Product 2"
+        );
+        assert_eq!(json["summary"]["overall"]["shown"], 2);
+        assert!(export_batch(&searches, &summary, &OutputFormat::Csv).is_err());
+    }
+
+    /// Tests the CSV export has a row for every tone or product without tones, and no summary.
+    #[test]
+    fn export_csv() {
+        let results_by_website = tear_up();
+        let summary = Summary::new(&results_by_website, &StatisticsByWebsite::new());
+        let output = export(&results_by_website, &summary, &OutputFormat::Csv).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(
+            lines,
+            vec![
+                "website,brand,name,tone,price_standard,price_sales,discount_percentage,rating,available,similarity,link",
+                "sephora-spain,Brand,Product 1,Tone 1,20.0,15.0,25,9.5,false,0.95,http://www.test.com/tone-1",
+                "maquillalia,,Product 2,,10.0,,,,true,0.5,http://www.test2.com",
+            ]
+        );
+    }
+
+    /// Tests the terminal format is not exported.
+    #[test]
+    fn export_terminal() {
+        assert!(export(&tear_up(), &Summary::default(), &OutputFormat::Terminal).is_err());
+    }
+
+    /// Tests products without any of the optional fields are exported.
+    #[test]
+    fn export_all_none_products() {
+        let mut results_by_website = ResultsByWebsite::new();
+        results_by_website.insert(
+            Website::SephoraSpain,
+            vec![
+                Product::default(),
+                Product {
+                    tones: Some(vec![Tone::default()]),
+                    ..Default::default()
+                },
+            ],
+        );
+        for output_format in [OutputFormat::Json, OutputFormat::Yaml] {
+            assert!(export(&results_by_website, &Summary::default(), &output_format).is_ok());
+        }
+        let csv = export(&results_by_website, &Summary::default(), &OutputFormat::Csv).unwrap();
+        assert_eq!(
+            csv.lines().skip(1).take(2).collect::<Vec<&str>>(),
+            vec![
+                "sephora-spain,,,,,,,,false,0.0,",
+                "sephora-spain,,,,,,,,false,0.0,"
+            ]
+        );
+    }
+}
@@ -0,0 +1,301 @@
+//! Filters of the search options applied to the products found, at tone level when they have tones.
+
+use scrapped_webs::brands;
+use scrapped_webs::product::{Product, Tone};
+
+use crate::parameters::FilterArgs;
+
+/// Conditions the products, or their tones, must satisfy to be in the results.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Filters {
+    /// Keep only the products and tones on sale.
+    pub on_sale: bool,
+    /// Minimum percentage of discount of the products and tones on sale.
+    pub min_discount: Option<u8>,
+    /// Keep only the available products and tones.
+    pub available_only: bool,
+    /// Minimum price to pay for the products and tones.
+    pub min_price: Option<f32>,
+    /// Maximum price to pay for the products and tones.
+    pub max_price: Option<f32>,
+    /// Keep only the products of these brands, every brand if it is empty.
+    pub brands: Vec<String>,
+    /// Discard the products of these brands.
+    pub exclude_brands: Vec<String>,
+}
+
+impl Filters {
+    /// Creates the filters of the search options.
+    pub fn new(args: &FilterArgs) -> Self {
+        Self {
+            on_sale: args.on_sale,
+            min_discount: args.min_discount,
+            available_only: args.available_only,
+            min_price: args.min_price,
+            max_price: args.max_price,
+            brands: args.brands.clone(),
+            exclude_brands: args.exclude_brands.clone(),
+        }
+    }
+
+    /// Returns true if there is no filter to apply.
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Returns the products that satisfy the filters.
+    /// Brands are compared without case and accents and using the known ways of writing every brand.
+    /// Tones that do not satisfy them are removed from the products,
+    /// and products without any remaining tone are discarded.
+    /// # Arguments
+    /// * `products` - The products found in a website.
+    /// # Returns
+    /// The products filtered.
+    pub fn filter(&self, products: Vec<Product>) -> Vec<Product> {
+        if self.is_empty() {
+            return products;
+        }
+        products
+            .into_iter()
+            .filter(|product| {
+                brands::is_accepted(product.brand.as_deref(), &self.brands, &self.exclude_brands)
+            })
+            .filter_map(|mut product| match product.tones.take() {
+                Some(tones) => {
+                    let tones: Vec<Tone> = tones
+                        .into_iter()
+                        .filter(|tone| {
+                            self.matches(
+                                tone.price(),
+                                tone.price_sales,
+                                tone.discount_percentage(),
+                                tone.available,
+                            )
+                        })
+                        .collect();
+                    if tones.is_empty() {
+                        None
+                    } else {
+                        product.tones = Some(tones);
+                        Some(product)
+                    }
+                }
+                None => self
+                    .matches(
+                        product.price(),
+                        product.price_sales,
+                        product.discount_percentage(),
+                        product.available,
+                    )
+                    .then_some(product),
+            })
+            .collect()
+    }
+
+    /// Returns true if a product without tones or a tone satisfies the filters.
+    /// # Arguments
+    /// * `price` - The price to pay for the product or tone, the price on sale if it is on sale.
+    /// * `price_sales` - The price on sale of the product or tone.
+    /// * `discount_percentage` - Its percentage of discount, None if it is unknown.
+    /// * `available` - True if it can be bought.
+    fn matches(
+        &self,
+        price: Option<f32>,
+        price_sales: Option<f32>,
+        discount_percentage: Option<u8>,
+        available: bool,
+    ) -> bool {
+        let on_sale = (!self.on_sale && self.min_discount.is_none())
+            || (price_sales.is_some()
+                && self.min_discount.is_none_or(|min_discount| {
+                    discount_percentage.is_some_and(|discount| discount >= min_discount)
+                }));
+        let price_range = (self.min_price.is_none() && self.max_price.is_none())
+            || price.is_some_and(|price| {
+                self.min_price.is_none_or(|min_price| price >= min_price)
+                    && self.max_price.is_none_or(|max_price| price <= max_price)
+            });
+        on_sale && price_range && (!self.available_only || available)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Creates a product for the tests.
+    fn product(
+        price_standard: Option<f32>,
+        price_sales: Option<f32>,
+        tones: Option<Vec<Tone>>,
+    ) -> Product {
+        Product::new(
+            String::from("Butter Gloss"),
+            Some(String::from("NYX")),
+            String::from("http://www.test.com"),
+            price_standard,
+            price_sales,
+            tones,
+            None,
+            0.9,
+            true,
+        )
+    }
+
+    /// Creates an available tone for the tests.
+    fn tone(name: &str, price_standard: f32, price_sales: Option<f32>) -> Tone {
+        Tone::new(
+            Some(String::from(name)),
+            Some(price_standard),
+            price_sales,
+            true,
+            None,
+            None,
+        )
+    }
+
+    /// Tests the prices to pay of the products without tones and of the tones are in the range.
+    #[test]
+    fn filter_price_range() {
+        let products = vec![
+            product(Some(30.0), Some(18.0), None),
+            product(Some(30.0), None, None),
+            product(None, None, None),
+            product(
+                Some(5.0),
+                None,
+                Some(vec![
+                    tone("Nude", 30.0, Some(20.0)),
+                    tone("Red", 12.0, None),
+                    tone("Pink", 25.0, None),
+                ]),
+            ),
+        ];
+        let filters = Filters {
+            min_price: Some(15.0),
+            max_price: Some(20.0),
+            ..Default::default()
+        };
+        let filtered = filters.filter(products.clone());
+        assert_eq!(filtered.len(), 2);
+        assert_eq!(filtered[0], products[0]);
+        assert_eq!(
+            filtered[1].tones,
+            Some(vec![tone("Nude", 30.0, Some(20.0))])
+        );
+
+        let filters = Filters {
+            max_price: Some(12.0),
+            ..Default::default()
+        };
+        let filtered = filters.filter(products);
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].tones, Some(vec![tone("Red", 12.0, None)]));
+    }
+
+    /// Tests only the available products and tones are kept, and the products without any of them are discarded.
+    #[test]
+    fn filter_available_only() {
+        let mut sold_out_product = product(Some(10.0), None, None);
+        sold_out_product.available = false;
+        let mut sold_out_tone = tone("Red", 10.0, None);
+        sold_out_tone.available = false;
+        let products = vec![
+            product(Some(10.0), None, None),
+            sold_out_product,
+            product(
+                None,
+                None,
+                Some(vec![tone("Nude", 10.0, None), sold_out_tone.clone()]),
+            ),
+            product(None, None, Some(vec![sold_out_tone])),
+        ];
+        let filters = Filters {
+            available_only: true,
+            ..Default::default()
+        };
+        let filtered = filters.filter(products.clone());
+        assert_eq!(filtered.len(), 2);
+        assert_eq!(filtered[0], products[0]);
+        assert_eq!(filtered[1].tones, Some(vec![tone("Nude", 10.0, None)]));
+    }
+
+    /// Tests only the products of the brands requested and not excluded are kept.
+    #[test]
+    fn filter_brands() {
+        let mut products = vec![
+            product(Some(10.0), None, None),
+            product(Some(10.0), None, None),
+            product(Some(10.0), None, None),
+        ];
+        products[1].brand = Some(String::from("Estée Lauder"));
+        products[2].brand = None;
+        let filters = Filters {
+            brands: vec![
+                String::from("nyx professional makeup"),
+                String::from("ESTEE LAUDER"),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(filters.filter(products.clone()), products[..2].to_vec());
+
+        let filters = Filters {
+            exclude_brands: vec![String::from("estee lauder")],
+            ..Default::default()
+        };
+        assert_eq!(
+            filters.filter(products.clone()),
+            vec![products[0].clone(), products[2].clone()]
+        );
+    }
+
+    /// Tests the products without tones are kept only if they are on sale with enough discount.
+    #[test]
+    fn filter_on_sale_products() {
+        let products = vec![
+            product(Some(10.0), Some(5.0), None),
+            product(Some(10.0), Some(9.0), None),
+            product(Some(10.0), None, None),
+            product(None, Some(7.0), None),
+        ];
+        let on_sale = Filters {
+            on_sale: true,
+            ..Default::default()
+        };
+        assert_eq!(on_sale.filter(products.clone()).len(), 3);
+
+        let min_discount = Filters {
+            min_discount: Some(20),
+            ..Default::default()
+        };
+        let filtered = min_discount.filter(products.clone());
+        assert_eq!(filtered, vec![products[0].clone()]);
+
+        assert_eq!(Filters::default().filter(products.clone()), products);
+    }
+
+    /// Tests only the tones on sale are kept, and the products without any of them are discarded.
+    #[test]
+    fn filter_on_sale_tones() {
+        let products = vec![
+            product(
+                None,
+                None,
+                Some(vec![
+                    tone("Nude", 10.0, None),
+                    tone("Red", 10.0, Some(7.0)),
+                    tone("Pink", 10.0, Some(9.0)),
+                ]),
+            ),
+            product(None, None, Some(vec![tone("Nude", 10.0, None)])),
+        ];
+        let filters = Filters {
+            on_sale: true,
+            min_discount: Some(30),
+            ..Default::default()
+        };
+        let filtered = filters.filter(products);
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].tones, Some(vec![tone("Red", 10.0, Some(7.0))]));
+    }
+}
@@ -0,0 +1,192 @@
+//! History of the searches, kept as a JSON line for every search.
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use scrapped_webs::locale::Locale;
+use serde::{Deserialize, Serialize};
+
+use crate::parameters::Website;
+use crate::summary::Summary;
+
+/// Name of the file of the history inside the data directory.
+const HISTORY_FILE: &str = "makeup-comparator/history.jsonl";
+
+/// A search done in the past.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Entry {
+    /// Seconds since the Unix epoch when the search was done.
+    pub timestamp: u64,
+    /// The product searched.
+    pub product: String,
+    /// The websites searched.
+    pub websites: Vec<Website>,
+    /// Number of products in the results.
+    pub results: usize,
+    /// The lowest price found.
+    pub cheapest: Option<f32>,
+}
+
+impl Entry {
+    /// Creates the entry of a search done now.
+    /// # Arguments
+    /// * `product` - The product searched, as written by the user with the modifiers of the query.
+    /// * `summary` - The statistics of the search.
+    pub fn new(product: &str, summary: &Summary) -> Self {
+        Self {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |duration| duration.as_secs()),
+            product: product.to_string(),
+            websites: summary.websites.keys().cloned().collect(),
+            results: summary.overall.shown,
+            cheapest: summary.overall.cheapest,
+        }
+    }
+
+    /// Formats the entry to be printed in terminal.
+    /// # Example
+    /// 2026-10-18 09:30 UTC - labial - sephora.es, maquillalia.com - 12 results from €5.00
+    pub fn terminal_format(&self, locale: Locale) -> String {
+        let websites: Vec<String> = self
+            .websites
+            .iter()
+            .map(|website| website.to_string())
+            .collect();
+        let mut out = format!(
+            "{} UTC - {} - {} - {} results",
+            date_time(self.timestamp),
+            self.product,
+            websites.join(", "),
+            self.results
+        );
+        if let Some(cheapest) = self.cheapest {
+            out.push_str(format!(" from {}", locale.price(cheapest)).as_str());
+        }
+        out
+    }
+}
+
+/// Returns the file of the history, in $XDG_DATA_HOME or ~/.local/share.
+/// None if neither of them is defined.
+pub fn path() -> Option<PathBuf> {
+    let data_directory = match std::env::var_os("XDG_DATA_HOME") {
+        Some(data_home) if !data_home.is_empty() => PathBuf::from(data_home),
+        _ => PathBuf::from(std::env::var_os("HOME")?).join(".local/share"),
+    };
+    Some(data_directory.join(HISTORY_FILE))
+}
+
+/// Adds a search at the end of the history, creating the file if it does not exist.
+pub fn record(path: &Path, entry: &Entry) -> Result<(), anyhow::Error> {
+    if let Some(directory) = path.parent() {
+        fs::create_dir_all(directory)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", serde_json::to_string(entry)?)?;
+    Ok(())
+}
+
+/// Returns the searches of the history from the oldest to the newest, none if the file does not exist.
+pub fn load(path: &Path) -> Result<Vec<Entry>, anyhow::Error> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    fs::read_to_string(path)?
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| Ok(serde_json::from_str(line)?))
+        .collect()
+}
+
+/// Formats seconds since the Unix epoch as a date and time in UTC.
+/// # Example
+/// 951868800 = 2000-03-01 00:00
+pub fn date_time(timestamp: u64) -> String {
+    // Days to civil date, from http://howardhinnant.github.io/date_algorithms.html
+    let days = (timestamp / 86400) as i64 + 719468;
+    let seconds = timestamp % 86400;
+    let era = days.div_euclid(146097);
+    let day_of_era = days - era * 146097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}",
+        seconds / 3600,
+        seconds % 3600 / 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests the searches are appended and loaded in order.
+    #[test]
+    fn record_and_load() {
+        let path = std::env::temp_dir()
+            .join(format!("makeup-comparator-{}", std::process::id()))
+            .join("history.jsonl");
+        let _ = fs::remove_file(&path);
+        assert_eq!(load(&path).unwrap(), Vec::new());
+
+        let entry1 = Entry {
+            timestamp: 951868800,
+            product: String::from("labial"),
+            websites: vec![Website::SephoraSpain, Website::Maquillalia],
+            results: 12,
+            cheapest: Some(5.0),
+        };
+        let entry2 = Entry {
+            timestamp: 951868860,
+            product: String::from("colorete"),
+            websites: vec![Website::Maquillalia],
+            results: 0,
+            cheapest: None,
+        };
+        record(&path, &entry1).unwrap();
+        record(&path, &entry2).unwrap();
+        assert_eq!(load(&path).unwrap(), vec![entry1, entry2]);
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    /// Tests the entries printed in terminal.
+    #[test]
+    fn entry_terminal_format() {
+        let mut entry = Entry {
+            timestamp: 951868800,
+            product: String::from("labial"),
+            websites: vec![Website::SephoraSpain, Website::Maquillalia],
+            results: 12,
+            cheapest: Some(5.0),
+        };
+        assert_eq!(
+            entry.terminal_format(Locale::EnGb),
+            "2000-03-01 00:00 UTC - labial - sephora.es, maquillalia.com - 12 results from €5.00"
+        );
+        entry.cheapest = None;
+        assert!(entry
+            .terminal_format(Locale::EsEs)
+            .ends_with("- 12 results"));
+    }
+
+    /// Tests the dates of some timestamps.
+    #[test]
+    fn date_time_all_cases() {
+        assert_eq!(date_time(0), "1970-01-01 00:00");
+        assert_eq!(date_time(951868800), "2000-03-01 00:00");
+        assert_eq!(date_time(951868799), "2000-02-29 23:59");
+        assert_eq!(date_time(1792314000), "2026-10-18 09:00");
+    }
+}
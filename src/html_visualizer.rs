@@ -0,0 +1,633 @@
+//! This file handles the generation of a self-contained HTML report.
+
+use clap::ArgEnum;
+use scrapped_webs::locale::{Label, Locale};
+use scrapped_webs::product::{Product, Tone, MISSING_FIELD};
+
+use crate::parameters::{SortKey, SortingType, Website};
+use crate::scraper_handler::ResultsByWebsite;
+use crate::sorting;
+use crate::summary::Summary;
+use crate::terminal_visualizer::{self, availability_mark, Offer, Row};
+
+/// Styles of the report, inlined so the file can be shared without any other asset.
+const STYLE: &str = r#"
+body { font-family: -apple-system, "Segoe UI", Roboto, sans-serif; margin: 2rem auto; max-width: 960px; color: #222; background: #fafafa; }
+header { display: flex; justify-content: space-between; align-items: baseline; flex-wrap: wrap; }
+h1 { font-size: 1.6rem; }
+section.product { background: #fff; border: 1px solid #e2e2e2; border-radius: 8px; padding: 1rem 1.5rem; margin-bottom: 1.5rem; }
+section.product h2 { font-size: 1.2rem; margin: 0 0 .5rem; }
+article.offer { border-top: 1px solid #eee; padding: .5rem 0; }
+article.offer h3 { font-size: 1rem; margin: .25rem 0; }
+.meta { color: #666; font-size: .9rem; margin: .25rem 0; }
+.available { color: #1a7f37; }
+.unavailable { color: #b42318; }
+del { color: #888; }
+.badge { background: #b42318; color: #fff; border-radius: 4px; font-size: .8rem; padding: 0 .35rem; }
+table { border-collapse: collapse; width: 100%; font-size: .9rem; }
+th, td { text-align: left; padding: .25rem .5rem; border-bottom: 1px solid #f0f0f0; }
+a { color: #6941c6; }
+section.summary { background: #fff; border: 1px solid #e2e2e2; border-radius: 8px; padding: 1rem 1.5rem; }
+section.summary td, section.summary th { text-align: right; }
+section.summary td:first-child, section.summary th:first-child { text-align: left; }
+"#;
+
+/// Sorts the products in the browser by the criteria selected, in the same directions as the CLI.
+const SCRIPT: &str = r#"
+const directions = { name: 1, brand: 1, price: 1, similarity: -1, rating: -1 };
+const select = document.getElementById("sort-by");
+function sortProducts(key) {
+  const container = document.getElementById("products");
+  const sections = Array.from(container.children);
+  sections.sort((a, b) => {
+    const textual = key === "name" || key === "brand";
+    const x = textual ? a.dataset[key] : parseFloat(a.dataset[key]);
+    const y = textual ? b.dataset[key] : parseFloat(b.dataset[key]);
+    // The products without a value go last in both directions.
+    if (!textual && (isNaN(x) || isNaN(y))) {
+      return isNaN(x) - isNaN(y);
+    }
+    const comparison = textual ? x.localeCompare(y) : (x > y) - (x < y);
+    return comparison * directions[key];
+  });
+  sections.forEach((section) => container.appendChild(section));
+}
+select.addEventListener("change", (event) => sortProducts(event.target.value));
+sortProducts(select.value);
+"#;
+
+/// Values of a product grouped from every website, used to sort the products of the report.
+struct Values<'a> {
+    /// The brand of the first product with brand, empty if none of them has brand.
+    brand: &'a str,
+    /// The cheapest price to pay of all the websites, None if none of them has price.
+    price: Option<f32>,
+    /// The highest similarity of all the websites.
+    similarity: f32,
+    /// The highest rating of all the websites, of the product or its tones, None if none of them has rating.
+    rating: Option<f32>,
+}
+
+impl<'a> Values<'a> {
+    /// Returns the values of the product of every website of a row.
+    fn new(row: &Row<'a>) -> Self {
+        let products = row.products.values();
+        Self {
+            brand: products
+                .clone()
+                .find_map(|product| product.brand.as_deref())
+                .unwrap_or_default(),
+            price: products
+                .clone()
+                .filter_map(|product| Offer::new(product).price())
+                .reduce(f32::min),
+            similarity: products
+                .clone()
+                .map(|product| product.similarity)
+                .fold(0.0, f32::max),
+            rating: products
+                .filter_map(|product| product.effective_rating())
+                .reduce(f32::max),
+        }
+    }
+}
+
+/// Returns the results as a single HTML file with the same product of every website grouped.
+/// The products are sorted by the sorting selected, as the script does when it changes.
+/// # Arguments
+/// * `results_by_website` - The products found for every website.
+/// * `summary` - The statistics of the search, shown after the products.
+/// * `product` - The product searched, used as title.
+/// * `sorting_type` - The sorting selected when the report is opened.
+/// * `locale` - The language of the report and its labels, and the format of the prices and numbers.
+pub fn format(
+    results_by_website: &ResultsByWebsite,
+    summary: &Summary,
+    product: &str,
+    sorting_type: &SortingType,
+    locale: Locale,
+) -> String {
+    let (_, rows) = terminal_visualizer::group_products(results_by_website);
+    let order = SortKey::new(sorting_type.clone()).order;
+    let mut rows: Vec<(Row, Values)> = rows
+        .into_iter()
+        .map(|row| {
+            let values = Values::new(&row);
+            (row, values)
+        })
+        .collect();
+    rows.sort_by(|(row1, values1), (row2, values2)| match sorting_type {
+        SortingType::Name => sorting::compare_values(
+            Some(row1.name.to_lowercase()),
+            Some(row2.name.to_lowercase()),
+            order,
+            Ord::cmp,
+        ),
+        SortingType::Brand => sorting::compare_values(
+            Some(values1.brand.trim().to_lowercase()),
+            Some(values2.brand.trim().to_lowercase()),
+            order,
+            Ord::cmp,
+        ),
+        SortingType::Price => {
+            sorting::compare_values(values1.price, values2.price, order, f32::total_cmp)
+        }
+        SortingType::Similarity => sorting::compare_values(
+            Some(values1.similarity),
+            Some(values2.similarity),
+            order,
+            f32::total_cmp,
+        ),
+        SortingType::Rating => {
+            sorting::compare_values(values1.rating, values2.rating, order, f32::total_cmp)
+        }
+    });
+
+    let mut out: String = format!("<!DOCTYPE html>\n<html lang=\"{locale}\">\n<head>\n");
+    out.push_str("<meta charset=\"utf-8\">\n");
+    out.push_str("<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n");
+    out.push_str(format!("<title>Makeup comparator - {}</title>\n", escape(product)).as_str());
+    out.push_str(format!("<style>{STYLE}</style>\n</head>\n<body>\n").as_str());
+
+    out.push_str("<header>\n");
+    out.push_str(
+        format!(
+            "<h1>{} \"{}\"</h1>\n",
+            locale.label(Label::ResultsFor),
+            escape(product)
+        )
+        .as_str(),
+    );
+    out.push_str(
+        format!(
+            "<label>{} <select id=\"sort-by\">",
+            locale.label(Label::SortBy)
+        )
+        .as_str(),
+    );
+    for variant in SortingType::value_variants() {
+        let selected = match variant == sorting_type {
+            true => " selected",
+            false => "",
+        };
+        out.push_str(format!("<option value=\"{variant}\"{selected}>{variant}</option>").as_str());
+    }
+    out.push_str("</select></label>\n</header>\n");
+
+    if rows.is_empty() {
+        out.push_str(format!("<p>{}</p>\n", locale.label(Label::NoProductsFound)).as_str());
+    }
+    out.push_str("<main id=\"products\">\n");
+    for (row, values) in rows.iter() {
+        // An empty value is not a number in the script, so the products without it go last.
+        let optional =
+            |value: Option<f32>| value.map(|value| value.to_string()).unwrap_or_default();
+        out.push_str(
+            format!(
+                "<section class=\"product\" data-name=\"{}\" data-brand=\"{}\" data-price=\"{}\" data-similarity=\"{}\" data-rating=\"{}\">\n",
+                escape(&row.name.to_lowercase()),
+                escape(&values.brand.trim().to_lowercase()),
+                optional(values.price),
+                values.similarity,
+                optional(values.rating),
+            )
+            .as_str(),
+        );
+        out.push_str(format!("<h2>{}</h2>\n", escape(&row.name)).as_str());
+        for (website, product) in row.products.iter() {
+            out.push_str(offer_html(website, product, locale).as_str());
+        }
+        out.push_str("</section>\n");
+    }
+    out.push_str("</main>\n");
+    out.push_str(summary_html(summary, locale).as_str());
+    out.push_str(format!("<script>{SCRIPT}</script>\n</body>\n</html>\n").as_str());
+    out
+}
+
+/// Returns the HTML of the summary of the search, a row for every website and one for all of them.
+fn summary_html(summary: &Summary, locale: Locale) -> String {
+    let mut out: String = format!(
+        "<section class=\"summary\">\n<h2>{}</h2>\n<table>\n",
+        locale.label(Label::Summary)
+    );
+    out.push_str(
+        header_row(
+            &[
+                Label::Website,
+                Label::Found,
+                Label::FilteredBySimilarity,
+                Label::Shown,
+                Label::Cheapest,
+                Label::MostExpensive,
+                Label::AveragePrice,
+                Label::OnSale,
+                Label::AverageDiscount,
+                Label::UnavailableTones,
+                Label::UnavailableProducts,
+                Label::Time,
+            ],
+            locale,
+        )
+        .as_str(),
+    );
+    out.push('\n');
+    let lines = summary
+        .websites
+        .iter()
+        .map(|(website, statistics)| (website.to_string(), statistics))
+        .chain(std::iter::once((
+            String::from(locale.label(Label::Overall)),
+            &summary.overall,
+        )));
+    for (name, statistics) in lines {
+        let optional_price = |price: Option<f32>| match price {
+            Some(price) => locale.price(price),
+            None => String::from(MISSING_FIELD),
+        };
+        out.push_str(
+            format!(
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}s</td></tr>\n",
+                escape(&name),
+                statistics.found,
+                statistics.filtered_by_similarity,
+                statistics.shown,
+                optional_price(statistics.cheapest),
+                optional_price(statistics.most_expensive),
+                optional_price(statistics.average_price),
+                statistics.discounts,
+                statistics
+                    .average_discount
+                    .map_or(String::new(), |discount| format!("-{discount:.0}%")),
+                statistics.unavailable_tones,
+                statistics.unavailable_products,
+                locale.number(statistics.time_seconds as f32, 2),
+            )
+            .as_str(),
+        );
+    }
+    out.push_str("</table>\n</section>\n");
+    out
+}
+
+/// Returns the HTML of a product in a website, with its tones if it has any.
+fn offer_html(website: &Website, product: &Product, locale: Locale) -> String {
+    let mut out: String = String::from("<article class=\"offer\">\n");
+    out.push_str(
+        format!(
+            "<h3><a href=\"{}\">{website}</a></h3>\n",
+            escape(&product.link)
+        )
+        .as_str(),
+    );
+    out.push_str(
+        format!(
+            "<p class=\"meta\">{} {}",
+            locale.label(Label::Similarity),
+            locale.percentage(product.similarity * 100.0)
+        )
+        .as_str(),
+    );
+    if let Some(rating) = product.rating {
+        out.push_str(format!(" · {}★", locale.number(rating, 1)).as_str());
+    }
+    out.push_str(format!(" · {}</p>\n", availability_html(product.available, locale)).as_str());
+
+    match product.tones.as_ref() {
+        Some(tones) => {
+            out.push_str(
+                format!(
+                    "<table>\n<thead>{}</thead>\n<tbody>\n",
+                    header_row(
+                        &[
+                            Label::Tone,
+                            Label::Price,
+                            Label::Availability,
+                            Label::Rating
+                        ],
+                        locale
+                    )
+                )
+                .as_str(),
+            );
+            for tone in tones {
+                out.push_str(tone_html(tone, locale).as_str());
+            }
+            out.push_str("</tbody>\n</table>\n");
+        }
+        None => {
+            out.push_str(format!("<p>{}</p>\n", price_html(&Offer::new(product), locale)).as_str())
+        }
+    }
+    out.push_str("</article>\n");
+    out
+}
+
+/// Returns the row of the tones table for a tone.
+fn tone_html(tone: &Tone, locale: Locale) -> String {
+    let name = escape(tone.name.as_deref().unwrap_or(MISSING_FIELD));
+    let name = match tone.url.as_ref() {
+        Some(url) => format!("<a href=\"{}\">{name}</a>", escape(url)),
+        None => name,
+    };
+    format!(
+        "<tr><td>{name}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+        price_html(&Offer::from(tone), locale),
+        availability_html(tone.available, locale),
+        tone.rating.map_or(String::new(), |rating| format!(
+            "{}★",
+            locale.number(rating, 1)
+        )),
+    )
+}
+
+/// Returns the price, with the standard one struck through and the discount if it is on sale.
+fn price_html(offer: &Offer, locale: Locale) -> String {
+    offer.price_format(
+        locale,
+        |price_standard, price_sales, discount_percentage| {
+            format!("<del>{price_standard}</del> <strong>{price_sales}</strong> <span class=\"badge\">-{discount_percentage}%</span>")
+        },
+        |price| format!("<strong>{price}</strong>"),
+    )
+}
+
+/// Returns the availability with its style.
+fn availability_html(available: bool, locale: Locale) -> String {
+    match available {
+        true => format!(
+            "<span class=\"available\">{} {}</span>",
+            availability_mark(true),
+            capitalize(locale.label(Label::Available))
+        ),
+        false => format!(
+            "<span class=\"unavailable\">{} {}</span>",
+            availability_mark(false),
+            capitalize(locale.label(Label::SoldOut))
+        ),
+    }
+}
+
+/// Returns the row of the headers of a table, with the labels capitalized.
+fn header_row(labels: &[Label], locale: Locale) -> String {
+    let headers: String = labels
+        .iter()
+        .map(|label| format!("<th>{}</th>", capitalize(locale.label(*label))))
+        .collect();
+    format!("<tr>{headers}</tr>")
+}
+
+/// Returns the text with its first letter in uppercase, e.g. sold out = Sold out.
+fn capitalize(text: &str) -> String {
+    let mut characters = text.chars();
+    match characters.next() {
+        Some(first) => first.to_uppercase().chain(characters).collect(),
+        None => String::new(),
+    }
+}
+
+/// Returns the text with the HTML special characters escaped.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::summary::Statistics;
+
+    /// Creates the results used in the tests, the first product is sold in both websites.
+    fn tear_up() -> ResultsByWebsite {
+        let tones = vec![
+            Tone::new(
+                Some(String::from("Joy <3")),
+                Some(26.99),
+                Some(20.24),
+                true,
+                Some(String::from("http://www.sephora.es/1?tone=joy&size=1")),
+                Some(9.0),
+            ),
+            Tone::new(
+                Some(String::from("Hope")),
+                Some(26.99),
+                None,
+                false,
+                None,
+                None,
+            ),
+        ];
+        let sephora_product = Product::new(
+            String::from("Soft Pinch Liquid Blush"),
+            Some(String::from("Rare Beauty")),
+            String::from("http://www.sephora.es/1"),
+            None,
+            None,
+            Some(tones),
+            Some(9.5),
+            0.9,
+            true,
+        );
+        let maquillalia_product = Product::new(
+            String::from("Soft Pinch Liquid Blush"),
+            Some(String::from("Rare Beauty")),
+            String::from("http://www.maquillalia.com/1"),
+            Some(24.95),
+            None,
+            None,
+            None,
+            0.85,
+            false,
+        );
+        let mut results_by_website = ResultsByWebsite::new();
+        results_by_website.insert(Website::SephoraSpain, vec![sephora_product]);
+        results_by_website.insert(Website::Maquillalia, vec![maquillalia_product]);
+        results_by_website
+    }
+
+    /// Tests the report groups the product of every website with its prices, tones and sorting.
+    #[test]
+    fn format_happy_path() {
+        let html = format(
+            &tear_up(),
+            &Summary::default(),
+            "blush",
+            &SortingType::Price,
+            Locale::EnGb,
+        );
+        assert!(html.starts_with("<!DOCTYPE html>\n<html lang=\"en-GB\">"));
+        assert!(html.contains("<h1>Results for \"blush\"</h1>"));
+        assert_eq!(html.matches("<section class=\"product\"").count(), 1);
+        assert_eq!(html.matches("<article class=\"offer\">").count(), 2);
+        assert!(html.contains("data-price=\"20.24\""));
+        assert!(html.contains("data-similarity=\"0.9\""));
+        assert!(html.contains("data-rating=\"9.5\""));
+        assert!(html.contains(
+            "<del>€26.99</del> <strong>€20.24</strong> <span class=\"badge\">-25%</span>"
+        ));
+        assert!(html.contains("<strong>€24.95</strong>"));
+        assert!(
+            html.contains("<a href=\"http://www.sephora.es/1?tone=joy&amp;size=1\">Joy &lt;3</a>")
+        );
+        assert!(html.contains("✘ Sold out"));
+        assert!(html.contains("<option value=\"price\" selected>price</option>"));
+        assert!(html.contains("<option value=\"rating\">rating</option>"));
+    }
+
+    /// Tests the report does not depend on external assets.
+    #[test]
+    fn format_self_contained() {
+        let html = format(
+            &tear_up(),
+            &Summary::default(),
+            "blush",
+            &SortingType::Similarity,
+            Locale::EnGb,
+        );
+        assert!(html.contains("<style>"));
+        assert!(html.contains("<script>"));
+        assert!(!html.contains("<link"));
+        assert!(!html.contains(" src="));
+    }
+
+    /// Tests the summary is shown after the products.
+    #[test]
+    fn format_summary() {
+        let mut summary = Summary::default();
+        summary.websites.insert(
+            Website::SephoraSpain,
+            Statistics {
+                found: 4,
+                filtered_by_similarity: 2,
+                shown: 1,
+                cheapest: Some(20.24),
+                average_discount: Some(25.0),
+                time_seconds: 1.5,
+                ..Default::default()
+            },
+        );
+        let html = format(
+            &tear_up(),
+            &summary,
+            "blush",
+            &SortingType::Price,
+            Locale::EnGb,
+        );
+        assert!(html.find("</main>").unwrap() < html.find("<section class=\"summary\">").unwrap());
+        assert!(html.contains(
+            "<tr><td>sephora.es</td><td>4</td><td>2</td><td>1</td><td>€20.24</td><td>N/A</td><td>N/A</td><td>0</td><td>-25%</td><td>0</td><td>0</td><td>1.50s</td></tr>"
+        ));
+        assert!(html.contains("<tr><td>Overall</td>"));
+    }
+
+    /// Tests the language, labels, prices and numbers of the report follow the locale.
+    #[test]
+    fn format_es_es() {
+        let html = format(
+            &tear_up(),
+            &Summary::default(),
+            "colorete",
+            &SortingType::Price,
+            Locale::EsEs,
+        );
+        assert!(html.starts_with("<!DOCTYPE html>\n<html lang=\"es-ES\">"));
+        assert!(html.contains("<h1>Resultados de \"colorete\"</h1>"));
+        assert!(html.contains("<label>Ordenar por <select"));
+        assert!(html.contains("Similitud 90,00 % · 9,5★ · "));
+        assert!(html.contains("<del>26,99 €</del> <strong>20,24 €</strong>"));
+        assert!(html.contains(
+            "<thead><tr><th>Tono</th><th>Precio</th><th>Disponibilidad</th><th>Valoración</th></tr></thead>"
+        ));
+        assert!(html.contains("✘ Agotado"));
+        assert!(html.contains("<h2>Resumen</h2>"));
+        assert!(html.contains("<th>Web</th><th>Encontrados</th><th>Filtrados por similitud</th>"));
+        assert!(html.contains("<tr><td>Total</td><td>0</td>"));
+        assert!(!html.contains("Results for"));
+    }
+
+    /// Tests the report without results.
+    #[test]
+    fn format_without_products() {
+        let html = format(
+            &ResultsByWebsite::new(),
+            &Summary::default(),
+            "<blush>",
+            &SortingType::Name,
+            Locale::EnGb,
+        );
+        assert!(html.contains("<p>No products found.</p>"));
+        assert!(html.contains("Results for \"&lt;blush&gt;\""));
+    }
+
+    /// Tests products without any of the optional fields are rendered with placeholders.
+    #[test]
+    fn format_all_none_products() {
+        let mut results_by_website = ResultsByWebsite::new();
+        results_by_website.insert(
+            Website::SephoraSpain,
+            vec![Product {
+                tones: Some(vec![Tone::default()]),
+                ..Default::default()
+            }],
+        );
+        results_by_website.insert(Website::Maquillalia, vec![Product::default()]);
+        let html = format(
+            &results_by_website,
+            &Summary::default(),
+            "",
+            &SortingType::Price,
+            Locale::EnGb,
+        );
+        assert!(html.contains("data-price=\"\" data-similarity=\"0\" data-rating=\"\""));
+        assert!(!html.contains("inf"));
+        assert!(html.contains("<tr><td>N/A</td><td>N/A</td>"));
+        assert!(html.contains("<p>N/A</p>"));
+    }
+
+    /// Tests the products without price or rating are after the ones with it in both directions.
+    #[test]
+    fn format_sorted_missing_last() {
+        let product = |name: &str, price: Option<f32>, rating: Option<f32>| Product {
+            name: String::from(name),
+            link: format!("https://www.test.com/{name}"),
+            price_standard: price,
+            rating,
+            ..Default::default()
+        };
+        let results_by_website = ResultsByWebsite::from([(
+            Website::Maquillalia,
+            vec![
+                product("Unpriced", None, Some(9.0)),
+                product("Expensive", Some(30.0), None),
+                product("Cheap", Some(10.0), Some(0.0)),
+            ],
+        )]);
+        let names = |sorting_type: SortingType| -> Vec<String> {
+            format(
+                &results_by_website,
+                &Summary::default(),
+                "",
+                &sorting_type,
+                Locale::EnGb,
+            )
+            .lines()
+            .filter_map(|line| line.strip_prefix("<h2>"))
+            .filter_map(|line| line.strip_suffix("</h2>"))
+            .filter(|name| *name != "Summary")
+            .map(String::from)
+            .collect()
+        };
+        assert_eq!(
+            names(SortingType::Price),
+            vec!["Cheap", "Expensive", "Unpriced"]
+        );
+        assert_eq!(
+            names(SortingType::Rating),
+            vec!["Unpriced", "Cheap", "Expensive"]
+        );
+        assert!(SCRIPT.contains("return isNaN(x) - isNaN(y);"));
+    }
+}
@@ -1,28 +1,28 @@
 #![allow(unused_imports)]
 use std::collections::HashMap;
 
+mod batch;
+mod commands;
+mod completions;
+mod config;
+mod exporter;
+mod filters;
+mod history;
+mod html_visualizer;
+mod man;
+mod markdown_visualizer;
 mod parameters;
 mod parameters_processor;
+mod query;
 mod scraper_handler;
+mod server;
+mod sorting;
+mod summary;
 mod terminal_visualizer;
+mod tui;
 
-use parameters_processor::ParametersProcessor;
-use scraper_handler::ScraperHandler;
-use scrapped_webs::configuration::Configuration;
-use scrapped_webs::product::Product;
-use scrapped_webs::scrappable::Scrappable;
-use scrapped_webs::webs::maquillalia::Maquillalia;
-use scrapped_webs::webs::sephora::spain::SephoraSpain;
-
-use clap::Parser;
-use parameters::Args;
-use scrapped_webs::product::Tone;
-
-use clap::Arg;
+use parameters::Cli;
 
 fn main() {
-    let parameters_processor = ParametersProcessor::new(Args::parse());
-    let scraper_handler = ScraperHandler::new(parameters_processor);
-    let results_by_website = scraper_handler.get_results();
-    terminal_visualizer::print(&results_by_website);
+    commands::run(Cli::parse());
 }
@@ -0,0 +1,145 @@
+//! Man page of the command line, generated from its definition
+//! so every new subcommand and option is documented.
+
+use crate::parameters::Cli;
+
+/// Returns the man page of the command line in roff, with the options of every subcommand.
+/// # Example
+/// makeup-comparator man --output-file makeup-comparator.1 && man ./makeup-comparator.1
+pub fn generate() -> String {
+    let mut command = Cli::command();
+    command.build();
+    let name = command.get_name();
+    let mut page = format!(
+        ".TH {} 1 \"\" \"{} {}\"\n.SH NAME\n{} \\- {}\n",
+        roff(&name.to_uppercase()),
+        roff(name),
+        command.get_version().unwrap_or_default(),
+        roff(name),
+        roff(command.get_about().unwrap_or_default())
+    );
+    page.push_str(&format!(
+        ".SH SYNOPSIS\n\\fB{}\\fR [\\fIOPTIONS\\fR] [\\fISUBCOMMAND\\fR]\n.SH OPTIONS\n",
+        roff(name)
+    ));
+    page.push_str(&options(&command, true));
+    page.push_str(".SH SUBCOMMANDS\n");
+    page.push_str(&subcommands(&command, ""));
+    page
+}
+
+/// Returns the sections of the subcommands of a command, recursively.
+fn subcommands(command: &clap::Command, parent: &str) -> String {
+    command
+        .get_subcommands()
+        .filter(|subcommand| !subcommand.is_hide_set())
+        .map(|subcommand| {
+            let path = format!("{parent}{} ", subcommand.get_name());
+            format!(
+                ".SS {}\n{}\n.RS\n{}.RE\n{}",
+                roff(path.trim_end()),
+                roff(subcommand.get_about().unwrap_or_default()),
+                options(subcommand, false),
+                subcommands(subcommand, &path)
+            )
+        })
+        .collect()
+}
+
+/// Returns the entries of the visible arguments of a command, the global ones only if `global` is true.
+fn options(command: &clap::Command, global: bool) -> String {
+    let mut entries = String::new();
+    let arguments = command
+        .get_arguments()
+        .filter(|arg| !arg.is_hide_set() && (global || !arg.is_global_set()));
+    for arg in arguments {
+        let value_name = arg
+            .get_value_names()
+            .and_then(|names| names.first().copied())
+            .unwrap_or_else(|| arg.get_id())
+            .to_uppercase();
+        let mut switches: Vec<String> = arg
+            .get_short()
+            .map(|short| format!("-{short}"))
+            .into_iter()
+            .chain(arg.get_long().map(|long| format!("--{long}")))
+            .map(|switch| format!("\\fB{}\\fR", roff(&switch)))
+            .collect();
+        match (arg.is_positional(), arg.is_takes_value_set()) {
+            (true, _) => switches.push(format!("\\fI{}\\fR", roff(&value_name))),
+            (false, true) => {
+                let last = switches.pop().unwrap_or_default();
+                switches.push(format!("{last}=\\fI{}\\fR", roff(&value_name)));
+            }
+            (false, false) => {}
+        }
+        let help = arg
+            .get_help()
+            .unwrap_or_default()
+            .split_whitespace()
+            .collect::<Vec<&str>>()
+            .join(" ");
+        entries.push_str(&format!(".TP\n{}\n{}\n", switches.join(", "), roff(&help)));
+        let defaults: Vec<String> = arg
+            .get_default_values()
+            .iter()
+            .map(|value| value.to_string_lossy().to_string())
+            .collect();
+        if !defaults.is_empty() {
+            entries.push_str(&format!(".br\nDefault: {}\n", roff(&defaults.join(", "))));
+        }
+        // Like in the help, the values are not listed when hidden, e.g. every sorting criterion.
+        if let Some(values) = arg
+            .get_value_parser()
+            .possible_values()
+            .filter(|_| !arg.is_hide_possible_values_set())
+        {
+            let values: Vec<&str> = values
+                .filter(|value| !value.is_hide_set())
+                .map(|value| value.get_name())
+                .collect();
+            entries.push_str(&format!(".br\nValues: {}\n", roff(&values.join(", "))));
+        }
+    }
+    entries
+}
+
+/// Escapes a text for roff: backslashes, dashes and the lines starting with a control character.
+fn roff(text: &str) -> String {
+    let text = text.replace('\\', "\\e").replace('-', "\\-");
+    match text.starts_with(['.', '\'']) {
+        true => format!("\\&{text}"),
+        false => text,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests the man page has the options of the program and of every subcommand.
+    #[test]
+    fn man_page() {
+        let page = generate();
+        assert!(page.starts_with(".TH MAKEUP\\-COMPARATOR 1"));
+        for expected in [
+            ".SH OPTIONS",
+            "\\fB\\-p\\fR, \\fB\\-\\-product\\fR=\\fIPRODUCT\\fR",
+            ".SS search",
+            ".SS config show",
+            "Values: all, sephora\\-spain, maquillalia",
+            "Default: 3600",
+        ] {
+            assert!(page.contains(expected), "man page without {expected}");
+        }
+        assert!(!page.contains("similarity:asc"));
+    }
+
+    /// Tests the text is escaped for roff.
+    #[test]
+    fn roff_escape() {
+        assert_eq!(roff("--max-results"), "\\-\\-max\\-results");
+        assert_eq!(roff(".hidden"), "\\&.hidden");
+        assert_eq!(roff("a\\b"), "a\\eb");
+    }
+}
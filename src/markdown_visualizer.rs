@@ -0,0 +1,394 @@
+//! This file handles the generation of GitHub-flavoured Markdown to paste the results in issues and chats.
+
+use scrapped_webs::locale::Locale;
+use scrapped_webs::product::{Product, Tone, MISSING_FIELD};
+
+use crate::parameters::Website;
+use crate::scraper_handler::ResultsByWebsite;
+use crate::summary::Summary;
+use crate::terminal_visualizer::{self, availability_mark, Offer};
+
+/// Returns the results as a Markdown table for every website, with the tones as sub-lists,
+/// a comparison table of the same product in every website if there are several websites,
+/// and the summary of the search, with the prices in the format of the locale.
+/// # Example
+/// ## sephora.es
+///
+/// | Similarity | Product | Brand | Price | Discount | Rating | Available |
+/// |---:|---|---|---|---:|---:|:---:|
+/// | 92.00% | [Soft Pinch Liquid Blush](<https://www.sephora.es/1>) | Rare Beauty | €20.24 - €26.99 | -25% | 9.5 | ✔ |
+///
+/// Tones of Soft Pinch Liquid Blush:
+/// - ✔ Joy: ~~€26.99~~ €20.24 (-25%)
+pub fn format(results_by_website: &ResultsByWebsite, summary: &Summary, locale: Locale) -> String {
+    let (websites, rows) = terminal_visualizer::group_products(results_by_website);
+    if websites.is_empty() {
+        return format!("No products found.\n\n{}", summary_table(summary, locale));
+    }
+
+    let mut out: String = String::new();
+    for website in websites.iter() {
+        out.push_str(website_table(website, &results_by_website[*website], locale).as_str());
+    }
+
+    if websites.len() > 1 {
+        out.push_str("## Comparison\n\n| Product |");
+        for website in websites.iter() {
+            out.push_str(format!(" {website} |").as_str());
+        }
+        out.push_str("\n|---|");
+        out.push_str("---|".repeat(websites.len()).as_str());
+        out.push('\n');
+        for row in rows.iter() {
+            let offers: Vec<Option<Offer>> = websites
+                .iter()
+                .map(|website| row.products.get(website).map(|product| Offer::new(product)))
+                .collect();
+            let best_price = offers
+                .iter()
+                .flatten()
+                .filter_map(Offer::price)
+                .reduce(f32::min);
+            out.push_str(format!("| {} |", escape(&row.name)).as_str());
+            for offer in offers.iter() {
+                let cell = match offer {
+                    Some(offer) => {
+                        let availability = availability_mark(offer.available);
+                        let price = price(offer, locale);
+                        match offer.price().is_some() && offer.price() == best_price {
+                            true => format!("{availability} **{price}**"),
+                            false => format!("{availability} {price}"),
+                        }
+                    }
+                    None => String::new(),
+                };
+                out.push_str(format!(" {cell} |").as_str());
+            }
+            out.push('\n');
+        }
+        out.push('\n');
+    }
+    out.push_str(summary_table(summary, locale).as_str());
+    out
+}
+
+/// Returns the table of the summary, a row for every website and one for all of them.
+pub fn summary_table(summary: &Summary, locale: Locale) -> String {
+    let mut out: String = String::from("## Summary\n\n");
+    out.push_str("| Website | Found | Filtered by similarity | Shown | Cheapest | Most expensive | Average price | On sale | Average discount | Unavailable tones | Unavailable products | Time |\n");
+    out.push_str("|---|---:|---:|---:|---:|---:|---:|---:|---:|---:|---:|---:|\n");
+    let lines = summary
+        .websites
+        .iter()
+        .map(|(website, statistics)| (website.to_string(), statistics))
+        .chain(std::iter::once((
+            String::from("**Overall**"),
+            &summary.overall,
+        )));
+    for (name, statistics) in lines {
+        let optional_price = |price: Option<f32>| match price {
+            Some(price) => locale.price(price),
+            None => String::from(MISSING_FIELD),
+        };
+        out.push_str(
+            format!(
+                "| {name} | {} | {} | {} | {} | {} | {} | {} | {} | {} | {} | {:.2}s |\n",
+                statistics.found,
+                statistics.filtered_by_similarity,
+                statistics.shown,
+                optional_price(statistics.cheapest),
+                optional_price(statistics.most_expensive),
+                optional_price(statistics.average_price),
+                statistics.discounts,
+                statistics
+                    .average_discount
+                    .map_or(String::new(), |discount| format!("-{discount:.0}%")),
+                statistics.unavailable_tones,
+                statistics.unavailable_products,
+                statistics.time_seconds,
+            )
+            .as_str(),
+        );
+    }
+    out
+}
+
+/// Returns the table of the products of a website followed by the tones of every product.
+fn website_table(website: &Website, products: &[Product], locale: Locale) -> String {
+    let mut out: String = format!("## {website}\n\n");
+    out.push_str("| Similarity | Product | Brand | Price | Discount | Rating | Available |\n");
+    out.push_str("|---:|---|---|---|---:|---:|:---:|\n");
+    for product in products.iter() {
+        // Products with tones show the range of prices and the highest discount of the tones.
+        let (price, discount_percentage) = match product.tones.as_ref() {
+            Some(tones) => (
+                price_range(tones, locale),
+                tones.iter().filter_map(Tone::discount_percentage).max(),
+            ),
+            None => (
+                price(&Offer::new(product), locale),
+                product.discount_percentage(),
+            ),
+        };
+        out.push_str(
+            format!(
+                "| {:.2}% | {} | {} | {price} | {} | {} | {} |\n",
+                product.similarity * 100.0,
+                link(&escape(product.name.trim()), &product.link),
+                escape(product.brand.as_deref().unwrap_or_default().trim()),
+                discount_percentage.map_or(String::new(), |discount| format!("-{discount}%")),
+                product
+                    .rating
+                    .map_or(String::new(), |rating| rating.to_string()),
+                availability_mark(Offer::new(product).available),
+            )
+            .as_str(),
+        );
+    }
+    out.push('\n');
+
+    for product in products.iter() {
+        if let Some(tones) = product.tones.as_ref() {
+            out.push_str(format!("Tones of {}:\n", escape(product.name.trim())).as_str());
+            for tone in tones {
+                let name = escape(tone.name.as_deref().unwrap_or(MISSING_FIELD));
+                let name = match tone.url.as_ref() {
+                    Some(url) => link(&name, url),
+                    None => name,
+                };
+                out.push_str(
+                    format!(
+                        "- {} {name}: {}",
+                        availability_mark(tone.available),
+                        price(&Offer::from(tone), locale)
+                    )
+                    .as_str(),
+                );
+                if let Some(rating) = tone.rating {
+                    out.push_str(format!(" - {rating}").as_str());
+                }
+                out.push('\n');
+            }
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// Returns the price, with the standard one struck through and the discount if it is on sale.
+fn price(offer: &Offer, locale: Locale) -> String {
+    offer.price_format(
+        locale,
+        |price_standard, price_sales, discount_percentage| {
+            format!("~~{price_standard}~~ {price_sales} (-{discount_percentage}%)")
+        },
+        str::to_string,
+    )
+}
+
+/// Returns the lowest and highest price of the tones.
+fn price_range(tones: &[Tone], locale: Locale) -> String {
+    let prices: Vec<f32> = tones.iter().filter_map(Tone::price).collect();
+    let lowest_price = prices.iter().copied().reduce(f32::min);
+    let highest_price = prices.iter().copied().reduce(f32::max);
+    match (lowest_price, highest_price) {
+        (Some(lowest_price), Some(highest_price)) if lowest_price < highest_price => {
+            format!(
+                "{} - {}",
+                locale.price(lowest_price),
+                locale.price(highest_price)
+            )
+        }
+        (Some(price), _) => locale.price(price),
+        _ => String::from(MISSING_FIELD),
+    }
+}
+
+/// Returns a link to the url, written between angle brackets so its spaces and parentheses do not end it.
+fn link(text: &str, url: &str) -> String {
+    format!(
+        "[{text}](<{}>)",
+        url.replace('<', "%3C").replace('>', "%3E")
+    )
+}
+
+/// Returns the text with the characters with a meaning in Markdown escaped.
+fn escape(text: &str) -> String {
+    let mut out: String = String::new();
+    for character in text.chars() {
+        if matches!(character, '\\' | '|' | '[' | ']' | '*' | '_' | '`') {
+            out.push('\\');
+        }
+        out.push(character);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::summary::Statistics;
+
+    /// Creates the results used in the tests, the first product is sold in both websites.
+    fn tear_up() -> ResultsByWebsite {
+        let sephora_product = Product::new(
+            String::from("Soft Pinch Liquid Blush"),
+            Some(String::from("Rare Beauty")),
+            String::from("https://www.sephora.es/1"),
+            None,
+            None,
+            Some(vec![
+                Tone::new(
+                    Some(String::from("Joy")),
+                    Some(26.99),
+                    Some(20.24),
+                    true,
+                    None,
+                    Some(9.0),
+                ),
+                Tone::new(
+                    Some(String::from("Hope | Mate")),
+                    Some(26.99),
+                    None,
+                    false,
+                    Some(String::from("https://www.sephora.es/1?tone=hope")),
+                    None,
+                ),
+            ]),
+            Some(9.5),
+            0.92,
+            true,
+        );
+        let maquillalia_product = Product::new(
+            String::from("Soft Pinch Liquid Blush"),
+            Some(String::from("Rare Beauty")),
+            String::from("https://www.maquillalia.com/1"),
+            Some(24.95),
+            None,
+            None,
+            None,
+            0.9,
+            false,
+        );
+        let mut results_by_website = ResultsByWebsite::new();
+        results_by_website.insert(Website::SephoraSpain, vec![sephora_product]);
+        results_by_website.insert(Website::Maquillalia, vec![maquillalia_product]);
+        results_by_website
+    }
+
+    /// Tests the tables of every website with the tones as sub-lists.
+    #[test]
+    fn format_website_tables() {
+        let markdown = format(&tear_up(), &Summary::default(), Locale::EnGb);
+        println!("{markdown}");
+        assert!(markdown.starts_with("## sephora.es\n\n| Similarity | Product |"));
+        assert!(markdown.contains(
+            "| 92.00% | [Soft Pinch Liquid Blush](<https://www.sephora.es/1>) | Rare Beauty | €20.24 - €26.99 | -25% | 9.5 | ✔ |"
+        ));
+        assert!(markdown.contains(
+            "Tones of Soft Pinch Liquid Blush:\n- ✔ Joy: ~~€26.99~~ €20.24 (-25%) - 9\n"
+        ));
+        assert!(markdown
+            .contains("- ✘ [Hope \\| Mate](<https://www.sephora.es/1?tone=hope>): €26.99\n"));
+        assert!(markdown.contains("## maquillalia.com\n"));
+        assert!(markdown.contains(
+            "| 90.00% | [Soft Pinch Liquid Blush](<https://www.maquillalia.com/1>) | Rare Beauty | €24.95 |  |  | ✘ |"
+        ));
+        assert!(!markdown.contains('\u{1b}'));
+
+        let markdown = format(&tear_up(), &Summary::default(), Locale::EsEs);
+        assert!(markdown.contains("| 20,24 € - 26,99 € |"));
+        assert!(markdown.contains("- ✔ Joy: ~~26,99 €~~ 20,24 € (-25%) - 9\n"));
+    }
+
+    /// Tests the comparison table highlights the lowest price.
+    #[test]
+    fn format_comparison_table() {
+        let markdown = format(&tear_up(), &Summary::default(), Locale::EnGb);
+        assert!(markdown.contains(
+            "## Comparison\n\n| Product | sephora.es | maquillalia.com |\n|---|---|---|\n"
+        ));
+        assert!(markdown.contains(
+            "| Rare Beauty Soft Pinch Liquid Blush | ✔ **~~€26.99~~ €20.24 (-25%)** | ✘ €24.95 |"
+        ));
+
+        let mut results_by_website = tear_up();
+        results_by_website.remove(&Website::Maquillalia);
+        assert!(
+            !format(&results_by_website, &Summary::default(), Locale::EnGb)
+                .contains("## Comparison")
+        );
+    }
+
+    /// Tests the summary table is after the results.
+    #[test]
+    fn format_summary_table() {
+        let results_by_website = tear_up();
+        let summary = Summary {
+            overall: Statistics {
+                found: 5,
+                filtered_by_similarity: 2,
+                shown: 2,
+                cheapest: Some(20.24),
+                most_expensive: Some(26.99),
+                average_price: Some(24.06),
+                discounts: 1,
+                average_discount: Some(25.0),
+                unavailable_tones: 2,
+                unavailable_products: 1,
+                time_seconds: 1.234,
+            },
+            ..Default::default()
+        };
+        let markdown = format(&results_by_website, &summary, Locale::EnGb);
+        assert!(markdown.find("## Comparison").unwrap() < markdown.find("## Summary").unwrap());
+        assert!(markdown.ends_with(
+            "| **Overall** | 5 | 2 | 2 | €20.24 | €26.99 | €24.06 | 1 | -25% | 2 | 1 | 1.23s |\n"
+        ));
+    }
+
+    /// Tests the output without results, only with the summary.
+    #[test]
+    fn format_without_products() {
+        let mut summary = Summary::default();
+        summary.websites.insert(
+            Website::SephoraSpain,
+            Statistics {
+                found: 3,
+                filtered_by_similarity: 1,
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            format(&ResultsByWebsite::new(), &summary, Locale::EnGb),
+            "No products found.\n\n## Summary\n\n| Website | Found | Filtered by similarity | Shown | Cheapest | Most expensive | Average price | On sale | Average discount | Unavailable tones | Unavailable products | Time |\n|---|---:|---:|---:|---:|---:|---:|---:|---:|---:|---:|---:|\n| sephora.es | 3 | 1 | 0 | N/A | N/A | N/A | 0 |  | 0 | 0 | 0.00s |\n| **Overall** | 0 | 0 | 0 | N/A | N/A | N/A | 0 |  | 0 | 0 | 0.00s |\n"
+        );
+    }
+
+    /// Tests products without any of the optional fields are rendered with placeholders.
+    #[test]
+    fn format_all_none_products() {
+        let mut results_by_website = ResultsByWebsite::new();
+        results_by_website.insert(
+            Website::SephoraSpain,
+            vec![Product {
+                tones: Some(vec![Tone::default()]),
+                ..Default::default()
+            }],
+        );
+        results_by_website.insert(Website::Maquillalia, vec![Product::default()]);
+        let markdown = format(&results_by_website, &Summary::default(), Locale::EnGb);
+        assert!(markdown.contains("| 0.00% | [](<>) |  | N/A |  |  | ✘ |"));
+        assert!(markdown.contains("- ✘ N/A: N/A\n"));
+        assert!(markdown.contains("| ✘ N/A |"));
+    }
+
+    /// Tests the urls with spaces, parentheses or angle brackets do not break the links.
+    #[test]
+    fn link_special_characters() {
+        assert_eq!(
+            link("Blush", "https://www.sephora.es/blush (rosa)?a=<b>"),
+            "[Blush](<https://www.sephora.es/blush (rosa)?a=%3Cb%3E>)"
+        );
+    }
+}
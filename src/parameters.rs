@@ -1,8 +1,19 @@
+use std::ffi::{OsStr, OsString};
 use std::fmt::{Display, Error, Formatter};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::OnceLock;
 
-use clap::{clap_derive::ArgEnum, Parser};
+use clap::builder::TypedValueParser;
+use clap::{
+    clap_derive::ArgEnum, CommandFactory, ErrorKind, FromArgMatches, Parser, PossibleValue,
+    Subcommand, ValueEnum,
+};
+use scrapped_webs::locale::Locale;
+use serde::{Deserialize, Serialize};
 
-#[derive(ArgEnum, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(ArgEnum, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum Website {
     /// All the websites
     All,
@@ -12,6 +23,34 @@ pub enum Website {
     Maquillalia,
 }
 
+impl Website {
+    /// Returns the website of a url, None if it is not a url of a supported website.
+    /// # Example
+    /// https://www.sephora.es/p/soft-pinch-liquid-blush-P10049030.html = SephoraSpain
+    pub fn from_url(url: &str) -> Option<Self> {
+        let url = url::Url::parse(url).ok()?;
+        let host = url.host_str()?;
+        let is_domain = |domain: &str| host == domain || host.ends_with(&format!(".{domain}"));
+        if is_domain("sephora.es") {
+            Some(Website::SephoraSpain)
+        } else if is_domain("maquillalia.com") {
+            Some(Website::Maquillalia)
+        } else {
+            None
+        }
+    }
+}
+
+impl Display for Website {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        match self {
+            Website::All => write!(f, "all"),
+            Website::SephoraSpain => write!(f, "sephora.es"),
+            Website::Maquillalia => write!(f, "maquillalia.com"),
+        }
+    }
+}
+
 #[derive(ArgEnum, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub enum SortingType {
     /// Name of the product
@@ -38,25 +77,439 @@ impl Display for SortingType {
     }
 }
 
+/// Direction of a sorting criterion.
+#[derive(ArgEnum, Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SortOrder {
+    /// From the lowest to the highest
+    Asc,
+    /// From the highest to the lowest
+    Desc,
+}
+
+/// A sorting criterion and its direction.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct SortKey {
+    pub sorting_type: SortingType,
+    pub order: SortOrder,
+}
+
+impl SortKey {
+    /// Returns the criterion in its usual direction:
+    /// ascending for the name, price and brand, descending for the similarity and rating.
+    pub fn new(sorting_type: SortingType) -> Self {
+        let order = match sorting_type {
+            SortingType::Name | SortingType::Price | SortingType::Brand => SortOrder::Asc,
+            SortingType::Similarity | SortingType::Rating => SortOrder::Desc,
+        };
+        Self {
+            sorting_type,
+            order,
+        }
+    }
+}
+
+impl Display for SortKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        let order = self.order.to_possible_value().unwrap();
+        write!(f, "{}:{}", self.sorting_type, order.get_name())
+    }
+}
+
+/// Sorting criteria from the most to the least important.
+/// # Example
+/// price:asc,rating:desc sorts by price and the products with the same price by rating.
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct SortBy(pub Vec<SortKey>);
+
+impl SortBy {
+    /// Returns the most important criterion.
+    pub fn primary(&self) -> &SortingType {
+        &self.0[0].sorting_type
+    }
+}
+
+impl From<SortingType> for SortBy {
+    fn from(sorting_type: SortingType) -> Self {
+        Self(vec![SortKey::new(sorting_type)])
+    }
+}
+
+impl FromStr for SortBy {
+    type Err = String;
+
+    /// Parses the criteria separated by commas, every one with an optional :asc or :desc.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let keys = s
+            .split(',')
+            .map(|key| {
+                let (sorting_type, order) = match key.trim().split_once(':') {
+                    Some((sorting_type, order)) => (sorting_type, Some(order)),
+                    None => (key.trim(), None),
+                };
+                let invalid = || {
+                    format!(
+                        "\"{}\" is not a sorting criterion, use name, price, similarity, brand or rating, optionally followed by :asc or :desc",
+                        key.trim()
+                    )
+                };
+                let sorting_type =
+                    SortingType::from_str(sorting_type.trim(), true).map_err(|_| invalid())?;
+                let mut key = SortKey::new(sorting_type);
+                if let Some(order) = order {
+                    key.order = SortOrder::from_str(order.trim(), true).map_err(|_| invalid())?;
+                }
+                Ok(key)
+            })
+            .collect::<Result<Vec<SortKey>, String>>()?;
+        Ok(Self(keys))
+    }
+}
+
+/// Parser of the sorting criteria that also lists every criterion alone and with both orders,
+/// e.g. price and price:asc, to complete them in the shells. The lists of criteria are not validated against them.
+#[derive(Clone)]
+pub struct SortByParser;
+
+impl SortByParser {
+    /// Returns the names of the criteria, computed once.
+    fn names() -> &'static [String] {
+        static NAMES: OnceLock<Vec<String>> = OnceLock::new();
+        NAMES.get_or_init(|| {
+            SortingType::value_variants()
+                .iter()
+                .flat_map(|sorting_type| {
+                    std::iter::once(sorting_type.to_string()).chain(
+                        SortOrder::value_variants().iter().map(move |order| {
+                            SortKey {
+                                sorting_type: sorting_type.clone(),
+                                order: *order,
+                            }
+                            .to_string()
+                        }),
+                    )
+                })
+                .collect()
+        })
+    }
+}
+
+impl TypedValueParser for SortByParser {
+    type Value = SortBy;
+
+    fn parse_ref(
+        &self,
+        cmd: &clap::Command,
+        arg: Option<&clap::Arg>,
+        value: &OsStr,
+    ) -> Result<Self::Value, clap::Error> {
+        let parser = |value: &str| SortBy::from_str(value);
+        parser.parse_ref(cmd, arg, value)
+    }
+
+    fn possible_values(&self) -> Option<Box<dyn Iterator<Item = PossibleValue<'static>> + '_>> {
+        Some(Box::new(
+            Self::names()
+                .iter()
+                .map(|name| PossibleValue::new(name.as_str())),
+        ))
+    }
+}
+
+impl TryFrom<String> for SortBy {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::from_str(&value)
+    }
+}
+
+impl Display for SortBy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        let keys: Vec<String> = self.0.iter().map(SortKey::to_string).collect();
+        write!(f, "{}", keys.join(","))
+    }
+}
+
+#[derive(ArgEnum, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum OutputFormat {
+    /// Human readable output for the terminal
+    Terminal,
+    /// Side-by-side comparison of the websites for the terminal
+    Table,
+    /// Self-contained HTML report to be opened in a browser
+    Html,
+    /// GitHub-flavoured Markdown tables to paste in issues and chats
+    Markdown,
+    /// JSON with the products grouped by website
+    Json,
+    /// CSV with a row for every tone or product without tones
+    Csv,
+    /// YAML with the products grouped by website
+    Yaml,
+}
+
+impl Display for OutputFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        match self {
+            OutputFormat::Terminal => write!(f, "terminal"),
+            OutputFormat::Table => write!(f, "table"),
+            OutputFormat::Html => write!(f, "html"),
+            OutputFormat::Markdown => write!(f, "markdown"),
+            OutputFormat::Json => write!(f, "json"),
+            OutputFormat::Csv => write!(f, "csv"),
+            OutputFormat::Yaml => write!(f, "yaml"),
+        }
+    }
+}
+
 /// A simple command line finder and comparator for makeups websites
-#[derive(Parser, Debug, Clone)]
-#[clap(author, version, about, long_about = None)]
+#[derive(Parser, Debug)]
+#[clap(
+    author,
+    version,
+    about,
+    long_about = None,
+    subcommand_negates_reqs = true
+)]
+struct CliDefinition {
+    #[clap(subcommand)]
+    command: Option<Command>,
+    /// Search without subcommand, `--product X` is the same as `search --product X`.
+    #[clap(flatten)]
+    search: Args,
+    #[clap(flatten)]
+    output: OutputArgs,
+    /// Profile of the configuration file with the default values of the search
+    #[clap(long, value_parser, global = true)]
+    profile: Option<String>,
+}
+
+/// The command line parsed, a search if there is no subcommand.
+#[derive(Debug)]
+pub struct Cli {
+    pub command: Command,
+    pub output: OutputArgs,
+    /// The profile of the configuration file requested.
+    pub profile: Option<String>,
+}
+
+impl Cli {
+    /// Parses the arguments of the process, exiting with the usage if they are not valid.
+    pub fn parse() -> Self {
+        Self::try_parse_from(std::env::args_os()).unwrap_or_else(|err| err.exit())
+    }
+
+    /// Returns the definition of the command line, e.g. to generate its completions.
+    pub fn command() -> clap::Command<'static> {
+        CliDefinition::command()
+    }
+
+    /// Parses some arguments, the first one being the name of the program.
+    /// # Returns
+    /// The command line, or an error if the arguments are not valid or the search options
+    /// are given both without and with a subcommand.
+    pub fn try_parse_from<I, T>(arguments: I) -> Result<Self, clap::Error>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<OsString> + Clone,
+    {
+        let mut definition = CliDefinition::command();
+        let matches = definition.try_get_matches_from_mut(arguments)?;
+        let (command, output_matches) = match matches.subcommand() {
+            Some((name, _)) if matches.contains_id("product") => {
+                return Err(definition.error(
+                    ErrorKind::ArgumentConflict,
+                    format!("--product can not be used before the subcommand {name}"),
+                ))
+            }
+            Some((_, subcommand_matches)) => {
+                (Command::from_arg_matches(&matches)?, subcommand_matches)
+            }
+            None => (Command::Search(Args::from_arg_matches(&matches)?), &matches),
+        };
+        Ok(Self {
+            command,
+            output: OutputArgs::from_arg_matches(output_matches)?,
+            profile: output_matches.get_one::<String>("profile").cloned(),
+        })
+    }
+}
+
+/// Workflows of the command line.
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Search a product in the websites
+    Search(Args),
+    /// Search a product and show the same product of every website side by side
+    Compare(Args),
+    /// Show the product of a url of a supported website
+    Lookup {
+        /// Url of the product page
+        #[clap(value_parser)]
+        url: String,
+    },
+    /// List the supported websites
+    Sites,
+    /// Show the previous searches
+    History {
+        /// Maximum number of searches to show, the most recent ones
+        #[clap(long, value_parser, default_value_t = 20)]
+        limit: usize,
+    },
+    /// Repeat a search periodically and show the products whose price changed
+    Watch {
+        #[clap(flatten)]
+        args: Args,
+        /// Seconds between searches
+        #[clap(long, value_parser, default_value_t = 3600)]
+        interval: u64,
+        /// Number of searches before exiting, forever if not provided
+        #[clap(long, value_parser)]
+        times: Option<usize>,
+    },
+    /// Search every product of a shopping list, a search with its options in every line, and summarize all of them
+    Batch {
+        /// File with the searches, the standard input if it is not provided or it is -
+        #[clap(value_parser)]
+        file: Option<PathBuf>,
+    },
+    /// Show the configuration file and the settings of the searches
+    Config {
+        #[clap(subcommand)]
+        command: ConfigCommand,
+    },
+    /// Serve the searches as JSON over HTTP: /search?product=X, /lookup?url=X and /sites
+    Serve {
+        /// Address to listen to
+        #[clap(long, value_parser, default_value = "127.0.0.1:8080")]
+        address: String,
+    },
+    /// Print the completions of the command line for a shell, e.g. makeup-comparator completions bash > ~/.local/share/bash-completion/completions/makeup-comparator
+    Completions {
+        /// Shell to complete
+        #[clap(value_parser)]
+        shell: Shell,
+    },
+    /// Print the man page of the command line
+    Man,
+}
+
+/// Shells with completions of the command line.
+#[derive(ArgEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+    Powershell,
+}
+
+/// Workflows of the configuration.
+#[derive(Subcommand, Debug)]
+pub enum ConfigCommand {
+    /// Print the settings used in the searches and where every one comes from
+    Show,
+}
+
+/// Options of a search
+#[derive(clap::Args, Debug, Clone)]
 pub struct Args {
-    /// Name of the product to search and compare
+    /// Name of the product to search and compare. Supports "phrases", -exclusions and brand:, price:, rating: and tone: qualifiers
     #[clap(short, long, value_parser)]
     pub product: String,
-    /// Maximum number of results
-    #[clap(long, value_parser, default_value_t = 50)]
-    pub max_results: usize,
-    /// Minimum similarity threshold
-    #[clap(long, value_parser, default_value_t = 0.0)]
-    pub min_similarity: f32,
+    /// Maximum number of results of all the websites together, the first ones of all of them sorted [default: no limit]
+    #[clap(long, value_parser)]
+    pub max_results: Option<usize>,
+    /// Maximum number of results of every website [default: 50]
+    #[clap(long, value_parser)]
+    pub max_results_per_site: Option<usize>,
+    /// Minimum similarity threshold [default: 0]
+    #[clap(long, value_parser)]
+    pub min_similarity: Option<f32>,
     /// Websites to search
     #[clap(long, value_parser)]
     pub websites: Vec<Website>,
-    /// Sorting criteria
-    #[clap(long, value_parser, default_value_t = SortingType::Similarity)]
-    pub sort_by: SortingType,
+    /// Sorting criteria separated by commas, e.g. price:asc,rating:desc [default: similarity:desc]
+    #[clap(long, value_parser = SortByParser, hide_possible_values = true)]
+    pub sort_by: Option<SortBy>,
+    #[clap(flatten)]
+    pub filters: FilterArgs,
+    /// Print how the similarity of every product was computed
+    #[clap(long)]
+    pub explain: bool,
+    /// Browse the results in an interactive terminal UI
+    #[clap(long)]
+    pub tui: bool,
+}
+
+impl Args {
+    /// Returns the arguments of the look up of a product url, which is retrieved instead of searched.
+    pub fn look_up(url: &str) -> Self {
+        Self {
+            product: url.to_string(),
+            max_results: None,
+            max_results_per_site: Some(1),
+            min_similarity: Some(0.0),
+            websites: Vec::new(),
+            sort_by: Some(SortBy::from(SortingType::Similarity)),
+            filters: FilterArgs::default(),
+            explain: false,
+            tui: false,
+        }
+    }
+}
+
+/// A search outside of the command line, e.g. in a query string or a line of a batch file, with the same options as the search subcommand
+#[derive(Parser, Debug)]
+pub struct SearchRequest {
+    #[clap(flatten)]
+    pub args: Args,
+}
+
+/// Options of a search to filter the products found
+#[derive(clap::Args, Debug, Clone, Default)]
+pub struct FilterArgs {
+    /// Keep only the products and tones on sale
+    #[clap(long)]
+    pub on_sale: bool,
+    /// Keep only the products and tones on sale with at least this percentage of discount
+    #[clap(long, value_parser = clap::value_parser!(u8).range(0..=100))]
+    pub min_discount: Option<u8>,
+    /// Keep only the available products and tones
+    #[clap(long, conflicts_with = "show-unavailable")]
+    pub available_only: bool,
+    /// Keep the unavailable products and tones, dimmed in the terminal output
+    #[clap(long)]
+    pub show_unavailable: bool,
+    /// Keep only the products and tones with a price to pay, the price on sale if they are on sale, of at least this
+    #[clap(long, value_parser)]
+    pub min_price: Option<f32>,
+    /// Keep only the products and tones with a price to pay, the price on sale if they are on sale, of at most this
+    #[clap(long, value_parser)]
+    pub max_price: Option<f32>,
+    /// Keep only the products of this brand, can be repeated to keep several brands
+    #[clap(long = "brand", value_parser)]
+    pub brands: Vec<String>,
+    /// Discard the products of this brand, can be repeated to discard several brands
+    #[clap(long = "exclude-brand", value_parser)]
+    pub exclude_brands: Vec<String>,
+}
+
+/// Options of the output, shared by every subcommand
+#[derive(clap::Args, Debug, Clone, Default)]
+pub struct OutputArgs {
+    /// Format of the results [default: terminal, table for compare]
+    #[clap(long, value_parser, global = true)]
+    pub output_format: Option<OutputFormat>,
+    /// File to write the results instead of the standard output
+    #[clap(long, value_parser, global = true)]
+    pub output_file: Option<PathBuf>,
+    /// Print without colours, emoji or escape sequences, only ASCII markers
+    #[clap(long, global = true)]
+    pub plain: bool,
+    /// Language and number format of the terminal output [es-ES, en-GB], detected from LANG by default
+    #[clap(long, value_parser, global = true)]
+    pub locale: Option<Locale>,
 }
 
 #[cfg(test)]
@@ -67,14 +520,193 @@ mod tests {
     fn initialize_args() {
         let args = Args {
             product: String::from("Pintalabios"),
-            max_results: 15,
-            min_similarity: 0.0,
+            max_results: Some(15),
+            max_results_per_site: Some(10),
+            min_similarity: Some(0.0),
             websites: vec![Website::All],
-            sort_by: SortingType::Price,
+            sort_by: Some(SortBy::from(SortingType::Price)),
+            filters: FilterArgs {
+                on_sale: true,
+                min_discount: Some(20),
+                available_only: true,
+                show_unavailable: false,
+                min_price: Some(5.0),
+                max_price: Some(20.0),
+                brands: vec![String::from("NYX")],
+                exclude_brands: Vec::new(),
+            },
+            explain: true,
+            tui: true,
+        };
+        let output = OutputArgs {
+            output_format: Some(OutputFormat::Json),
+            output_file: Some(PathBuf::from("results.json")),
+            plain: true,
+            locale: Some(Locale::EsEs),
         };
         assert_eq!(args.product, "Pintalabios");
-        assert_eq!(args.max_results, 15);
-        assert_eq!(args.min_similarity, 0.0);
+        assert_eq!(args.max_results, Some(15));
+        assert_eq!(args.max_results_per_site, Some(10));
+        assert_eq!(args.min_similarity, Some(0.0));
         assert_eq!(args.websites, vec![Website::All]);
+        assert!(args.filters.on_sale);
+        assert_eq!(args.filters.min_discount, Some(20));
+        assert!(args.filters.available_only);
+        assert_eq!(args.filters.min_price, Some(5.0));
+        assert_eq!(args.filters.max_price, Some(20.0));
+        assert!(args.explain);
+        assert!(args.tui);
+        assert_eq!(output.output_format, Some(OutputFormat::Json));
+        assert_eq!(output.output_file, Some(PathBuf::from("results.json")));
+        assert!(output.plain);
+        assert_eq!(output.locale, Some(Locale::EsEs));
+    }
+
+    /// Tests `--product X` without subcommand still searches.
+    #[test]
+    fn parse_without_subcommand() {
+        let cli =
+            Cli::try_parse_from(["makeup-comparator", "--product", "labial", "--plain"]).unwrap();
+        assert!(matches!(cli.command, Command::Search(args) if args.product == "labial"));
+        assert!(cli.output.plain);
+        assert_eq!(cli.profile, None);
+        assert!(Cli::try_parse_from(["makeup-comparator"]).is_err());
+    }
+
+    /// Tests the subcommands with the global options before or after them.
+    #[test]
+    fn parse_subcommands() {
+        let cli = Cli::try_parse_from([
+            "makeup-comparator",
+            "--locale",
+            "es-ES",
+            "search",
+            "-p",
+            "labial",
+            "--output-format",
+            "json",
+            "--brand",
+            "nyx",
+            "--brand",
+            "Estée Lauder",
+            "--exclude-brand",
+            "dior",
+        ])
+        .unwrap();
+        match cli.command {
+            Command::Search(args) => {
+                assert_eq!(args.product, "labial");
+                assert_eq!(args.filters.brands, vec!["nyx", "Estée Lauder"]);
+                assert_eq!(args.filters.exclude_brands, vec!["dior"]);
+            }
+            command => panic!("Unexpected command {command:?}"),
+        }
+        assert_eq!(cli.output.locale, Some(Locale::EsEs));
+        assert_eq!(cli.output.output_format, Some(OutputFormat::Json));
+
+        let cli = Cli::try_parse_from([
+            "makeup-comparator",
+            "lookup",
+            "https://www.sephora.es/p/1.html",
+        ])
+        .unwrap();
+        assert!(
+            matches!(cli.command, Command::Lookup { url } if url == "https://www.sephora.es/p/1.html")
+        );
+
+        let cli =
+            Cli::try_parse_from(["makeup-comparator", "watch", "-p", "labial", "--times", "2"])
+                .unwrap();
+        assert!(matches!(
+            cli.command,
+            Command::Watch {
+                interval: 3600,
+                times: Some(2),
+                ..
+            }
+        ));
+
+        assert!(matches!(
+            Cli::try_parse_from(["makeup-comparator", "sites"])
+                .unwrap()
+                .command,
+            Command::Sites
+        ));
+        let cli =
+            Cli::try_parse_from(["makeup-comparator", "config", "show", "--profile", "quick"])
+                .unwrap();
+        assert!(matches!(
+            cli.command,
+            Command::Config {
+                command: ConfigCommand::Show
+            }
+        ));
+        assert_eq!(cli.profile, Some(String::from("quick")));
+        assert!(matches!(
+            Cli::try_parse_from(["makeup-comparator", "completions", "powershell"])
+                .unwrap()
+                .command,
+            Command::Completions {
+                shell: Shell::Powershell
+            }
+        ));
+        assert!(Cli::try_parse_from(["makeup-comparator", "completions", "tcsh"]).is_err());
+        assert!(Cli::try_parse_from(["makeup-comparator", "-p", "labial", "sites"]).is_err());
+        assert!(Cli::try_parse_from([
+            "makeup-comparator",
+            "-p",
+            "labial",
+            "--available-only",
+            "--show-unavailable"
+        ])
+        .is_err());
+    }
+
+    /// Tests the sorting criteria with and without direction, and the ones not valid.
+    #[test]
+    fn sort_by_from_str() {
+        let sort_by = SortBy::from_str("price:asc, Rating:DESC,similarity").unwrap();
+        assert_eq!(
+            sort_by.0,
+            vec![
+                SortKey::new(SortingType::Price),
+                SortKey::new(SortingType::Rating),
+                SortKey::new(SortingType::Similarity)
+            ]
+        );
+        assert_eq!(sort_by.primary(), &SortingType::Price);
+        assert_eq!(sort_by.to_string(), "price:asc,rating:desc,similarity:desc");
+        assert_eq!(
+            SortBy::from_str("name:desc").unwrap().0[0].order,
+            SortOrder::Desc
+        );
+        assert_eq!(
+            SortBy::from_str("price:up").unwrap_err(),
+            "\"price:up\" is not a sorting criterion, use name, price, similarity, brand or rating, optionally followed by :asc or :desc"
+        );
+        assert!(SortBy::from_str("").is_err());
+        assert!(SortBy::from_str("price,").is_err());
+        assert!(
+            Cli::try_parse_from(["makeup-comparator", "-p", "labial", "--sort-by", "stock"])
+                .is_err()
+        );
+    }
+
+    /// Tests the website of the urls.
+    #[test]
+    fn website_from_url() {
+        assert_eq!(
+            Website::from_url("https://www.sephora.es/p/soft-pinch-P1.html"),
+            Some(Website::SephoraSpain)
+        );
+        assert_eq!(
+            Website::from_url("https://maquillalia.com/rare-beauty.html"),
+            Some(Website::Maquillalia)
+        );
+        assert_eq!(
+            Website::from_url("https://www.notsephora.es/p/1.html"),
+            None
+        );
+        assert_eq!(Website::from_url("sephora.es"), None);
     }
 }
@@ -1,11 +1,19 @@
 //! Utilities to handle the command send via command line or translated via server petition
 
 use std::collections::HashMap;
+use std::path::PathBuf;
 
-use crate::parameters::{self, Args};
+use crate::{
+    config::{Config, Settings},
+    filters::Filters,
+    parameters::{self, Args, OutputArgs},
+    query::Query,
+};
 use clap::Parser;
 use scrapped_webs::{
+    colour::ColourMode,
     configuration::{self, Configuration},
+    locale::Locale,
     product::Product,
     scrappable::Scrappable,
     webs::{maquillalia::Maquillalia, sephora::spain::SephoraSpain},
@@ -15,32 +23,66 @@ use scrapped_webs::{
 #[derive(Debug)]
 pub struct ParametersProcessor {
     configuration: Configuration,
+    max_results: Option<usize>,
     websites: Vec<parameters::Website>,
     product: String,
-    sorting_type: parameters::SortingType,
+    query: Query,
+    filters: Filters,
+    sort_by: parameters::SortBy,
+    explain: bool,
+    show_unavailable: bool,
+    output_format: parameters::OutputFormat,
+    output_file: Option<PathBuf>,
+    tui: bool,
+    plain: bool,
+    locale: Locale,
 }
 
 impl ParametersProcessor {
     /// Creates a new ParametersProcessor.
     /// # Arguments
-    /// * `args` - The arguments sent by the user.
+    /// * `args` - The search arguments sent by the user.
+    /// * `output` - The output arguments sent by the user.
+    /// * `config` - The configuration file and environment, for the search settings not sent by the user.
     /// # Returns
     /// A new ParametersProcessor.
-    pub fn new(args: Args) -> Self {
-        let mut min_similarity = args.min_similarity;
+    pub fn new(args: Args, output: OutputArgs, config: &Config) -> Self {
+        let settings = config.resolve(&Settings::from_args(&args));
+        let mut min_similarity = settings.min_similarity.value;
         if min_similarity > 1.0 {
             min_similarity = 1.0;
         }
-        let mut max_results = args.max_results;
-        if max_results > configuration::MAX_RESULTS {
-            max_results = configuration::MAX_RESULTS;
+        let max_results = settings.max_results.value;
+        let mut max_results_per_site = settings.max_results_per_site.value;
+        if max_results_per_site > configuration::MAX_RESULTS {
+            max_results_per_site = configuration::MAX_RESULTS;
         }
-        let conf: Configuration = Configuration::new(min_similarity, max_results);
+        let brands = settings.brands.value;
+        let exclude_brands = settings.exclude_brands.value;
+        // The websites skip the product pages of other brands, which are also filtered after scraping.
+        let conf: Configuration = Configuration::new(min_similarity, max_results_per_site)
+            .with_brands(brands.clone(), exclude_brands.clone());
         Self {
             configuration: conf,
-            websites: args.websites,
+            max_results,
+            websites: settings.websites.value,
+            query: Query::parse(&args.product),
             product: args.product,
-            sorting_type: args.sort_by,
+            filters: Filters {
+                brands,
+                exclude_brands,
+                ..Filters::new(&args.filters)
+            },
+            sort_by: settings.sort_by.value,
+            explain: args.explain,
+            show_unavailable: args.filters.show_unavailable,
+            output_format: output
+                .output_format
+                .unwrap_or(parameters::OutputFormat::Terminal),
+            output_file: output.output_file,
+            tui: args.tui,
+            plain: output.plain,
+            locale: output.locale.unwrap_or_else(Locale::detect),
         }
     }
 
@@ -49,42 +91,111 @@ impl ParametersProcessor {
         &self.configuration
     }
 
-    /// Returns the product to search.
+    /// Returns the maximum number of results of all the websites together, None if there is no limit.
+    pub fn max_results(&self) -> Option<usize> {
+        self.max_results
+    }
+
+    /// Returns the product to search as written by the user, with the modifiers of the query.
+    /// The free text matched against the products is the one of the query.
     pub fn product(&self) -> &String {
         &self.product
     }
 
+    /// Returns the query parsed from the product argument.
+    pub fn query(&self) -> &Query {
+        &self.query
+    }
+
+    /// Returns the filters of the search options.
+    pub fn filters(&self) -> &Filters {
+        &self.filters
+    }
+
+    /// Returns the queries to search, the product first and its translations after it.
+    pub fn query_variants(&self) -> Vec<String> {
+        self.query.variants()
+    }
+
     /// Returns the websites to search.
     pub fn websites(&self) -> &Vec<parameters::Website> {
         &self.websites
     }
 
-    /// Returns the sorting type.
-    pub fn sorting_type(&self) -> &parameters::SortingType {
-        &self.sorting_type
+    /// Returns the sorting criteria.
+    pub fn sort_by(&self) -> &parameters::SortBy {
+        &self.sort_by
+    }
+
+    /// Returns true if the similarity of every product must be explained.
+    pub fn explain(&self) -> bool {
+        self.explain
+    }
+
+    /// Returns true if the unavailable products and tones are dimmed in the terminal output.
+    pub fn show_unavailable(&self) -> bool {
+        self.show_unavailable
+    }
+
+    /// Returns the format of the results.
+    pub fn output_format(&self) -> &parameters::OutputFormat {
+        &self.output_format
+    }
+
+    /// Returns the file to write the results, None for the standard output.
+    pub fn output_file(&self) -> Option<&PathBuf> {
+        self.output_file.as_ref()
+    }
+
+    /// Returns true if the results are browsed in the interactive UI.
+    pub fn tui(&self) -> bool {
+        self.tui
+    }
+
+    /// Returns the language and number format of the terminal output.
+    pub fn locale(&self) -> Locale {
+        self.locale
+    }
+
+    /// Returns how the terminal output is painted.
+    /// Plain if it was requested or the results are written to a file, detected from the environment otherwise.
+    pub fn colour_mode(&self) -> ColourMode {
+        match self.plain || self.output_file.is_some() {
+            true => ColourMode::Plain,
+            false => ColourMode::detect(),
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::parameters::FilterArgs;
 
     /// Setups the test.
     fn tear_up(
         product: String,
-        max_results: usize,
+        max_results_per_site: usize,
         min_similarity: f32,
         websites: Vec<parameters::Website>,
         sort_by: parameters::SortingType,
     ) -> ParametersProcessor {
         let args = Args {
             product,
-            max_results,
-            min_similarity,
+            max_results: None,
+            max_results_per_site: Some(max_results_per_site),
+            min_similarity: Some(min_similarity),
             websites,
-            sort_by,
+            sort_by: Some(parameters::SortBy::from(sort_by)),
+            filters: FilterArgs::default(),
+            explain: false,
+            tui: false,
+        };
+        let output = OutputArgs {
+            locale: Some(Locale::EnGb),
+            ..Default::default()
         };
-        ParametersProcessor::new(args)
+        ParametersProcessor::new(args, output, &Config::default())
     }
 
     /// Tests the new method with a happy path.
@@ -106,6 +217,49 @@ mod tests {
         );
     }
 
+    /// Tests the settings not sent by the user have the default values.
+    #[test]
+    fn new_default_settings() {
+        let args = Args {
+            product: String::from("Pintalabios"),
+            max_results: None,
+            max_results_per_site: None,
+            min_similarity: None,
+            websites: Vec::new(),
+            sort_by: None,
+            filters: FilterArgs::default(),
+            explain: false,
+            tui: false,
+        };
+        let parameters_processor =
+            ParametersProcessor::new(args, OutputArgs::default(), &Config::default());
+        assert_eq!(parameters_processor.configuration().max_results(), 50);
+        assert_eq!(parameters_processor.max_results(), None);
+        assert_eq!(parameters_processor.configuration().min_similarity(), 0.0);
+        assert_eq!(
+            parameters_processor.sort_by(),
+            &parameters::SortBy::from(parameters::SortingType::Similarity)
+        );
+        assert!(parameters_processor.websites().is_empty());
+    }
+
+    /// Tests the plain mode is used when requested or when the results go to a file.
+    #[test]
+    fn colour_mode_plain() {
+        let mut parameters_processor = tear_up(
+            String::from("Pintalabios"),
+            15,
+            0.0,
+            vec![parameters::Website::SephoraSpain],
+            parameters::SortingType::Similarity,
+        );
+        parameters_processor.plain = true;
+        assert_eq!(parameters_processor.colour_mode(), ColourMode::Plain);
+        parameters_processor.plain = false;
+        parameters_processor.output_file = Some(PathBuf::from("results.txt"));
+        assert_eq!(parameters_processor.colour_mode(), ColourMode::Plain);
+    }
+
     /// Tests the new method with a max results greater than the max allowed.
     #[test]
     fn new_max_results_greater_than_max_allowed() {
@@ -122,6 +276,26 @@ mod tests {
         );
     }
 
+    /// Tests the maximum of results of every website is kept when the one of all of them together is lower,
+    /// as they are only cut after merging and sorting the results of every website.
+    #[test]
+    fn new_max_results_lower_than_per_site() {
+        for (max_results, max_results_per_site) in [(10, 15), (20, 15)] {
+            let args = Args {
+                max_results: Some(max_results),
+                max_results_per_site: Some(15),
+                ..Args::look_up("Pintalabios")
+            };
+            let parameters_processor =
+                ParametersProcessor::new(args, OutputArgs::default(), &Config::default());
+            assert_eq!(parameters_processor.max_results(), Some(max_results));
+            assert_eq!(
+                parameters_processor.configuration().max_results(),
+                max_results_per_site
+            );
+        }
+    }
+
     /// Tests the new method with a min similarity greater than 1.
     #[test]
     fn new_min_similarity_greater_than_1() {
@@ -187,4 +361,41 @@ mod tests {
         );
         assert_eq!(*parameters_processor.product(), product);
     }
+
+    /// Tests the query method.
+    #[test]
+    fn query() {
+        let parameters_processor = tear_up(
+            String::from("Pintalabios brand:Milani price:<20"),
+            15,
+            0.0,
+            vec![parameters::Website::SephoraSpain],
+            parameters::SortingType::Similarity,
+        );
+        assert_eq!(
+            parameters_processor.product(),
+            "Pintalabios brand:Milani price:<20"
+        );
+        assert_eq!(parameters_processor.query().text, "Pintalabios");
+        assert_eq!(
+            parameters_processor.query().brand,
+            Some(String::from("Milani"))
+        );
+    }
+
+    /// Tests the query_variants method.
+    #[test]
+    fn query_variants() {
+        let parameters_processor = tear_up(
+            String::from("concealer"),
+            15,
+            0.0,
+            vec![parameters::Website::SephoraSpain],
+            parameters::SortingType::Similarity,
+        );
+        assert_eq!(
+            parameters_processor.query_variants(),
+            vec![String::from("concealer"), String::from("corrector")]
+        );
+    }
 }
@@ -0,0 +1,549 @@
+//! Search query language used in the `--product` argument.
+//!
+//! A query is made of free text words sent to the websites plus some optional modifiers:
+//! - `"quoted phrases"` that must appear in the product name.
+//! - `-term` words that must not appear in the product name.
+//! - `brand:`, `price:`, `rating:` and `tone:` qualifiers applied to the products found.
+//!
+//! Known brands written in the free text are detected and used as if they were a `brand:` qualifier.
+//!
+//! # Example
+//! `"kind words" -gloss brand:"rare beauty" price:<20 rating:>=8 tone:nude`
+
+use scrapped_webs::{
+    brands,
+    product::{Product, Tone},
+    similarity::{self, SimilarityExplanation, SimilarityStrategy},
+    synonyms,
+};
+
+/// Comparison used by the numeric qualifiers such as `price:<20`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Comparison {
+    Less(f32),
+    LessOrEqual(f32),
+    Greater(f32),
+    GreaterOrEqual(f32),
+    Equal(f32),
+}
+
+impl Comparison {
+    /// Parses a comparison like `<20`, `>=4.5` or `10`.
+    /// # Arguments
+    /// * `text` - The comparison without the qualifier name.
+    /// # Returns
+    /// The comparison or None if the value is not a number.
+    pub fn parse(text: &str) -> Option<Self> {
+        let (constructor, value): (fn(f32) -> Self, &str) =
+            if let Some(value) = text.strip_prefix("<=") {
+                (Self::LessOrEqual, value)
+            } else if let Some(value) = text.strip_prefix(">=") {
+                (Self::GreaterOrEqual, value)
+            } else if let Some(value) = text.strip_prefix('<') {
+                (Self::Less, value)
+            } else if let Some(value) = text.strip_prefix('>') {
+                (Self::Greater, value)
+            } else {
+                (Self::Equal, text.strip_prefix('=').unwrap_or(text))
+            };
+        value
+            .trim()
+            .replace(',', ".")
+            .parse::<f32>()
+            .ok()
+            .map(constructor)
+    }
+
+    /// Returns true if the value satisfies the comparison.
+    pub fn matches(&self, value: f32) -> bool {
+        match *self {
+            Self::Less(limit) => value < limit,
+            Self::LessOrEqual(limit) => value <= limit,
+            Self::Greater(limit) => value > limit,
+            Self::GreaterOrEqual(limit) => value >= limit,
+            Self::Equal(limit) => value == limit,
+        }
+    }
+}
+
+/// Search query parsed from the user input.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Query {
+    /// Free text, phrases included and brand excluded, used to search and to score the products.
+    pub text: String,
+    /// Phrases that must appear in the brand and name of the product.
+    pub phrases: Vec<String>,
+    /// Words that must not appear in the brand and name of the product.
+    pub exclusions: Vec<String>,
+    /// Brand the product must belong to, from the qualifier or detected in the free text.
+    pub brand: Option<String>,
+    /// Condition for the price of the product or its tones.
+    pub price: Option<Comparison>,
+    /// Condition for the rating of the product, in the same 0-10 scale it is printed.
+    pub rating: Option<Comparison>,
+    /// Text the name of the tones must contain.
+    pub tone: Option<String>,
+}
+
+impl Query {
+    /// Parses the query provided by the user.
+    /// Qualifiers with invalid values are reported and ignored.
+    /// # Arguments
+    /// * `input` - The query provided by the user.
+    /// # Returns
+    /// The parsed query.
+    pub fn parse(input: &str) -> Self {
+        let mut query = Query::default();
+        let mut text: Vec<String> = Vec::new();
+
+        for (token, quoted) in tokenize(input) {
+            if quoted {
+                text.push(token.clone());
+                query.phrases.push(token);
+            } else if let Some(exclusion) = token.strip_prefix('-') {
+                // A lone dash excludes nothing and is not searched either.
+                if !exclusion.is_empty() {
+                    query.exclusions.push(exclusion.to_string());
+                }
+            } else if let Some((qualifier, value)) = token
+                .split_once(':')
+                .filter(|(qualifier, _)| QUALIFIERS.contains(&qualifier.to_lowercase().as_str()))
+            {
+                query.add_qualifier(&qualifier.to_lowercase(), value);
+            } else {
+                text.push(token);
+            }
+        }
+
+        query.text = text.join(" ");
+        if query.brand.is_none() {
+            if let Some((brand, text)) = brands::detect_brand(&query.text) {
+                query.brand = Some(brand.to_string());
+                query.text = text;
+            }
+        }
+        query
+    }
+
+    /// Returns the free text and its translations to other languages.
+    pub fn variants(&self) -> Vec<String> {
+        synonyms::expand_query(&self.text)
+    }
+
+    /// Returns the text to send to the websites search for a variant of the free text.
+    /// The brand of the query is kept in the search text: the websites also search the brand of the products,
+    /// so their limited results are of that brand instead of other brands filtered later,
+    /// and it is the whole search when the free text is empty (e.g. `brand:Milani`).
+    /// It is only removed from the free text to score the product names.
+    /// # Arguments
+    /// * `variant` - One of the variants of the free text.
+    pub fn search_text(&self, variant: &str) -> String {
+        match self.brand.as_ref() {
+            Some(brand) => format!("{brand} {variant}").trim().to_string(),
+            None => variant.to_string(),
+        }
+    }
+
+    /// Returns the similarity between the product and the best-matching variant of the query and how it was computed.
+    /// If the query has a brand, it is already used as a filter so only the product name is scored.
+    /// # Arguments
+    /// * `product` - The product found.
+    pub fn explain_similarity(&self, product: &Product) -> SimilarityExplanation {
+        let variants = self.variants();
+        let full_name = format!(
+            "{} {}",
+            product.brand.as_deref().unwrap_or_default(),
+            product.name
+        );
+        let search_texts: Vec<String> = variants
+            .iter()
+            .map(|variant| self.search_text(variant))
+            .collect();
+        let full_name_explanation =
+            similarity::explain(&full_name, &search_texts, SimilarityStrategy::FullName);
+
+        if self.brand.is_none() || self.text.is_empty() {
+            return full_name_explanation;
+        }
+        let mut explanation =
+            similarity::explain(&product.name, &variants, SimilarityStrategy::NameOnly);
+        explanation.brand_bonus = explanation.score - full_name_explanation.score;
+        explanation
+    }
+
+    /// Stores the value of a qualifier.
+    fn add_qualifier(&mut self, qualifier: &str, value: &str) {
+        if value.is_empty() {
+            eprintln!("Qualifier \"{qualifier}:\" without value, ignoring it");
+            return;
+        }
+        match qualifier {
+            "brand" => self.brand = Some(value.to_string()),
+            "tone" => self.tone = Some(value.to_string()),
+            "price" | "rating" => match Comparison::parse(value) {
+                Some(comparison) if qualifier == "price" => self.price = Some(comparison),
+                Some(comparison) => self.rating = Some(comparison),
+                None => eprintln!("Invalid value for \"{qualifier}:{value}\", ignoring it"),
+            },
+            _ => unreachable!("Unknown qualifier {qualifier}"),
+        }
+    }
+
+    /// Returns the products that satisfy the phrases, exclusions and qualifiers of the query.
+    /// Tones that do not satisfy the price and tone qualifiers are removed from the products,
+    /// and products without any remaining tone are discarded.
+    /// # Arguments
+    /// * `products` - The products found in a website.
+    /// # Returns
+    /// The products filtered.
+    pub fn filter(&self, products: Vec<Product>) -> Vec<Product> {
+        products
+            .into_iter()
+            .filter(|product| self.matches_product(product))
+            .filter_map(|mut product| {
+                if self.price.is_none() && self.tone.is_none() {
+                    return Some(product);
+                }
+                match product.tones.take() {
+                    Some(tones) => {
+                        let tones: Vec<Tone> = tones
+                            .into_iter()
+                            .filter(|tone| self.matches_tone(tone))
+                            .collect();
+                        if tones.is_empty() {
+                            None
+                        } else {
+                            product.tones = Some(tones);
+                            Some(product)
+                        }
+                    }
+                    // A product without tones can not satisfy a tone qualifier.
+                    None if self.tone.is_some() => None,
+                    None => self.price_matches(product.price()).then_some(product),
+                }
+            })
+            .collect()
+    }
+
+    /// Returns true if the brand, name and rating of the product satisfy the query.
+    /// The rating is the best one of the tones when the product is only rated through its tones.
+    fn matches_product(&self, product: &Product) -> bool {
+        let full_name = similarity::normalize(&format!(
+            "{} {}",
+            product.brand.as_deref().unwrap_or_default(),
+            product.name
+        ));
+        let brand_matches = self.brand.as_ref().is_none_or(|brand| {
+            product
+                .brand
+                .as_ref()
+                .is_some_and(|product_brand| brands::same_brand(product_brand, brand))
+        });
+        let rating_matches = self.rating.is_none_or(|rating| {
+            product
+                .effective_rating()
+                .is_some_and(|value| rating.matches(value))
+        });
+
+        brand_matches
+            && rating_matches
+            && self
+                .phrases
+                .iter()
+                .all(|phrase| contains_words(&full_name, phrase))
+            && !self
+                .exclusions
+                .iter()
+                .any(|exclusion| contains_words(&full_name, exclusion))
+    }
+
+    /// Returns true if the name and price of the tone satisfy the query.
+    fn matches_tone(&self, tone: &Tone) -> bool {
+        let tone_matches = self.tone.as_ref().is_none_or(|tone_query| {
+            tone.name.as_ref().is_some_and(|name| {
+                similarity::normalize(name).contains(&similarity::normalize(tone_query))
+            })
+        });
+        tone_matches && self.price_matches(tone.price())
+    }
+
+    /// Returns true if there is no price qualifier or the price satisfies it.
+    fn price_matches(&self, price: Option<f32>) -> bool {
+        self.price
+            .is_none_or(|comparison| price.is_some_and(|price| comparison.matches(price)))
+    }
+}
+
+/// Names of the qualifiers supported in the query.
+const QUALIFIERS: [&str; 4] = ["brand", "price", "rating", "tone"];
+
+/// Splits the input in tokens by whitespaces, keeping together the text between quotes.
+/// # Returns
+/// The tokens without quotes and a flag indicating if the whole token was a quoted phrase.
+fn tokenize(input: &str) -> Vec<(String, bool)> {
+    let mut tokens: Vec<(String, bool)> = Vec::new();
+    let mut token = String::new();
+    let mut in_quotes = false;
+    let mut quoted = false;
+
+    for character in input.chars() {
+        match character {
+            '"' => {
+                in_quotes = !in_quotes;
+                quoted = quoted || token.is_empty();
+            }
+            c if c.is_whitespace() && !in_quotes => {
+                if !token.is_empty() {
+                    tokens.push((std::mem::take(&mut token), quoted));
+                }
+                quoted = false;
+            }
+            c => token.push(c),
+        }
+    }
+    if !token.is_empty() {
+        tokens.push((token, quoted));
+    }
+    tokens
+}
+
+/// Returns true if the normalized text contains all the words of the phrase consecutively.
+fn contains_words(normalized_text: &str, phrase: &str) -> bool {
+    format!(" {normalized_text} ").contains(&format!(" {} ", similarity::normalize(phrase)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Creates a product for the tests.
+    fn product(brand: &str, name: &str, price: Option<f32>, tones: Option<Vec<Tone>>) -> Product {
+        Product::new(
+            String::from(name),
+            Some(String::from(brand)),
+            String::from("http://www.test.com"),
+            price,
+            None,
+            tones,
+            Some(8.0),
+            0.9,
+            true,
+        )
+    }
+
+    /// Creates a tone for the tests.
+    fn tone(name: &str, price_standard: f32, price_sales: Option<f32>) -> Tone {
+        Tone::new(
+            Some(String::from(name)),
+            Some(price_standard),
+            price_sales,
+            true,
+            None,
+            None,
+        )
+    }
+
+    /// Tests the parsing of a query with all the features.
+    #[test]
+    fn parse_all_features() {
+        let query = Query::parse(
+            r#""kind words" labial -gloss brand:"Rare Beauty" price:<20 rating:>=8 tone:nude"#,
+        );
+        assert_eq!(query.text, "kind words labial");
+        assert_eq!(query.phrases, vec![String::from("kind words")]);
+        assert_eq!(query.exclusions, vec![String::from("gloss")]);
+        assert_eq!(query.brand, Some(String::from("Rare Beauty")));
+        assert_eq!(query.price, Some(Comparison::Less(20.0)));
+        assert_eq!(query.rating, Some(Comparison::GreaterOrEqual(8.0)));
+        assert_eq!(query.tone, Some(String::from("nude")));
+    }
+
+    /// Tests a plain query is kept as it is.
+    #[test]
+    fn parse_plain_text() {
+        let query = Query::parse("Labial  líquido SPF:50");
+        assert_eq!(query.text, "Labial líquido SPF:50");
+        assert_eq!(
+            query,
+            Query {
+                text: String::from("Labial líquido SPF:50"),
+                ..Default::default()
+            }
+        );
+    }
+
+    /// Tests the parsing of invalid or incomplete qualifiers.
+    #[test]
+    fn parse_invalid_qualifiers() {
+        let query = Query::parse("price:<cheap rating: brand:Milani -");
+        assert_eq!(query.price, None);
+        assert_eq!(query.rating, None);
+        assert_eq!(query.text, "");
+        assert!(query.exclusions.is_empty());
+        let query = Query::parse("labial - mate");
+        assert_eq!(query.text, "labial mate");
+        let query = Query::parse("brand:Milani");
+        assert_eq!(query.text, "");
+        assert_eq!(query.search_text(&query.text), "Milani");
+    }
+
+    /// Tests the detection of the brand in the free text.
+    #[test]
+    fn parse_detects_brand() {
+        let query = Query::parse("Rare Beauty colorete");
+        assert_eq!(query.text, "colorete");
+        assert_eq!(query.brand, Some(String::from("Rare Beauty")));
+        assert_eq!(query.search_text("blush"), "Rare Beauty blush");
+
+        // The qualifier has priority over the detection.
+        let query = Query::parse("maybelline labial brand:milani");
+        assert_eq!(query.text, "maybelline labial");
+        assert_eq!(query.brand, Some(String::from("milani")));
+    }
+
+    /// Tests the similarity only scores the product name when the query has a brand.
+    #[test]
+    fn similarity_with_and_without_brand() {
+        let blush = product("RARE BEAUTY", "Colorete", Some(20.0), None);
+        assert_eq!(
+            Query::parse("Rare Beauty colorete")
+                .explain_similarity(&blush)
+                .score,
+            1.0
+        );
+        assert_eq!(
+            Query::parse("rare beauty blush")
+                .explain_similarity(&blush)
+                .score,
+            1.0
+        );
+        assert!(Query::parse("colorete").explain_similarity(&blush).score < 1.0);
+    }
+
+    /// Tests the explanation of the similarity with and without brand.
+    #[test]
+    fn explain_similarity_with_and_without_brand() {
+        let blush = product("RARE BEAUTY", "Soft Pinch Colorete", Some(20.0), None);
+
+        let explanation = Query::parse("Rare Beauty colorete").explain_similarity(&blush);
+        assert_eq!(explanation.strategy, SimilarityStrategy::NameOnly);
+        assert!(["colorete", "blush", "rubor"].contains(&explanation.normalized_query.as_str()));
+        assert_eq!(explanation.normalized_title, "soft pinch colorete");
+        let full_name_score = similarity::best_similarity(
+            "RARE BEAUTY Soft Pinch Colorete",
+            &[
+                String::from("Rare Beauty colorete"),
+                String::from("Rare Beauty blush"),
+                String::from("Rare Beauty rubor"),
+            ],
+        );
+        assert_eq!(explanation.brand_bonus, explanation.score - full_name_score);
+
+        let explanation = Query::parse("colorete").explain_similarity(&blush);
+        assert_eq!(explanation.strategy, SimilarityStrategy::FullName);
+        assert_eq!(
+            explanation.normalized_title,
+            "rare beauty soft pinch colorete"
+        );
+        assert_eq!(explanation.brand_bonus, 0.0);
+    }
+
+    /// Tests the parsing and matching of all the comparisons.
+    #[test]
+    fn comparison_all_cases() {
+        assert!(Comparison::parse("<20").unwrap().matches(19.99));
+        assert!(!Comparison::parse("<20").unwrap().matches(20.0));
+        assert!(Comparison::parse("<=20").unwrap().matches(20.0));
+        assert!(Comparison::parse(">4").unwrap().matches(4.5));
+        assert!(Comparison::parse(">=4,5").unwrap().matches(4.5));
+        assert!(Comparison::parse("=10").unwrap().matches(10.0));
+        assert!(Comparison::parse("10").unwrap().matches(10.0));
+        assert_eq!(Comparison::parse(">"), None);
+    }
+
+    /// Tests the filter by phrases, exclusions, brand and rating.
+    #[test]
+    fn filter_products() {
+        let products = vec![
+            product("Rare Beauty", "Kind Words Labial Mate", Some(20.0), None),
+            product("Rare Beauty", "Kind Words Gloss", Some(20.0), None),
+            product("Milani", "Kind Words Labial", Some(10.0), None),
+            product("Rare Beauty", "Words Kind Labial", Some(20.0), None),
+        ];
+        let filtered = Query::parse(r#""kind words" -gloss brand:"rare beauty" rating:>7"#)
+            .filter(products.clone());
+        assert_eq!(filtered, vec![products[0].clone()]);
+
+        let filtered = Query::parse("rare beauty labial -gloss").filter(products.clone());
+        assert_eq!(filtered, vec![products[0].clone(), products[3].clone()]);
+
+        let filtered = Query::parse("labial rating:>9").filter(products);
+        assert!(filtered.is_empty());
+    }
+
+    /// Tests the rating qualifier uses the tones of the products without rating of their own.
+    #[test]
+    fn filter_rating_of_tones() {
+        let rated_tone = |rating: f32| {
+            Tone::new(
+                Some(String::from("Nude")),
+                Some(10.0),
+                None,
+                true,
+                None,
+                Some(rating),
+            )
+        };
+        let mut products = vec![
+            product(
+                "Milani",
+                "Labial",
+                None,
+                Some(vec![rated_tone(7.0), rated_tone(8.5)]),
+            ),
+            product("Milani", "Gloss", None, Some(vec![rated_tone(6.0)])),
+            product(
+                "Milani",
+                "Colorete",
+                None,
+                Some(vec![tone("Nude", 10.0, None)]),
+            ),
+        ];
+        for product in products.iter_mut() {
+            product.rating = None;
+        }
+        let filtered = Query::parse("milani rating:>=8").filter(products.clone());
+        assert_eq!(filtered, vec![products[0].clone()]);
+    }
+
+    /// Tests the filter by price and tone removes the tones that do not match.
+    #[test]
+    fn filter_tones() {
+        let products = vec![
+            product(
+                "Milani",
+                "Labial",
+                None,
+                Some(vec![
+                    tone("Nude 01", 30.0, Some(15.0)),
+                    tone("Nude 02", 30.0, None),
+                    tone("Red 01", 10.0, None),
+                ]),
+            ),
+            product("Milani", "Colorete", Some(12.0), None),
+            product("Milani", "Polvos", None, None),
+        ];
+
+        let filtered = Query::parse("milani price:<20").filter(products.clone());
+        assert_eq!(filtered.len(), 2);
+        let tones = filtered[0].tones.as_ref().unwrap();
+        assert_eq!(tones.len(), 2);
+        assert_eq!(tones[0].name.as_deref(), Some("Nude 01"));
+        assert_eq!(tones[1].name.as_deref(), Some("Red 01"));
+        assert_eq!(filtered[1].name, "Colorete");
+
+        let filtered = Query::parse("milani tone:nude price:<20").filter(products);
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].tones.as_ref().unwrap().len(), 1);
+    }
+}
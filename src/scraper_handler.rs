@@ -1,10 +1,12 @@
 //! Handle the scraping of the data from the web page.
 
 use std::collections::HashMap;
+use std::time::{Duration, Instant};
 
 use crate::{
-    parameters::{self, Args},
+    parameters::{self, Args, OutputArgs},
     parameters_processor::{self, ParametersProcessor},
+    sorting,
 };
 use clap::Parser;
 use scrapped_webs::{
@@ -14,12 +16,30 @@ use scrapped_webs::{
     webs::{maquillalia::Maquillalia, sephora::spain::SephoraSpain},
 };
 
-type ResultsByWebsite = HashMap<parameters::Website, Vec<Product>>;
+/// Products found for every website.
+pub type ResultsByWebsite = HashMap<parameters::Website, Vec<Product>>;
+
+/// How the search went in a website, what is not kept in the products.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SearchStatistics {
+    /// Products found in the search of the website, of the brands accepted.
+    pub found: usize,
+    /// Products found discarded for being below the minimum similarity,
+    /// by the website or after scoring them against the query.
+    pub filtered_by_similarity: usize,
+    /// Time spent searching.
+    pub elapsed: Duration,
+}
+
+/// Statistics of the search in every website.
+pub type StatisticsByWebsite = HashMap<parameters::Website, SearchStatistics>;
 
 #[derive(Debug)]
 pub struct ScraperHandler {
     /// The configuration for the program.
     parameters_processor: ParametersProcessor,
+    /// Statistics of the last search in every website.
+    statistics_by_website: StatisticsByWebsite,
 }
 
 impl ScraperHandler {
@@ -31,128 +51,213 @@ impl ScraperHandler {
     pub fn new(parameters_processor: ParametersProcessor) -> Self {
         Self {
             parameters_processor,
+            statistics_by_website: StatisticsByWebsite::new(),
         }
     }
 
+    /// Returns the parameters used for the search.
+    pub fn parameters_processor(&self) -> &ParametersProcessor {
+        &self.parameters_processor
+    }
+
+    /// Returns the products found and the time spent in every website in the last search.
+    pub fn statistics_by_website(&self) -> &StatisticsByWebsite {
+        &self.statistics_by_website
+    }
+
     /// Returns the results of the search.
+    /// The products found and the time spent in every website are kept in the statistics.
     /// # Returns
     /// A HashMap with the results of the search.
     /// The key is the website and the value is a vector of products.
-    pub fn get_results(&self) -> ResultsByWebsite {
+    pub fn get_results(&mut self) -> ResultsByWebsite {
         let mut results_by_website = ResultsByWebsite::new();
+        self.statistics_by_website.clear();
 
+        let mut websites: Vec<parameters::Website> = Vec::new();
         for web in self.parameters_processor.websites().iter() {
             match web {
-                parameters::Website::SephoraSpain => {
-                    results_by_website.insert(
-                        parameters::Website::SephoraSpain,
-                        self.get_results_sephora_spain(),
-                    );
-                }
-                parameters::Website::Maquillalia => {
-                    results_by_website.insert(
-                        parameters::Website::Maquillalia,
-                        self.get_results_maquillalia(),
-                    );
-                }
-                parameters::Website::All => {
-                    results_by_website.insert(
-                        parameters::Website::SephoraSpain,
-                        self.get_results_sephora_spain(),
-                    );
-                    results_by_website.insert(
-                        parameters::Website::Maquillalia,
-                        self.get_results_maquillalia(),
-                    );
-                }
+                parameters::Website::All => websites.extend([
+                    parameters::Website::SephoraSpain,
+                    parameters::Website::Maquillalia,
+                ]),
+                web => websites.push(web.clone()),
             }
         }
+        // Every website is searched once, even if it is requested several times.
+        websites.sort();
+        websites.dedup();
+        for website in websites {
+            let start = Instant::now();
+            let (products, mut search_statistics) = match website {
+                parameters::Website::SephoraSpain => self.get_results_sephora_spain(),
+                parameters::Website::Maquillalia => self.get_results_maquillalia(),
+                parameters::Website::All => unreachable!("All is expanded to every website"),
+            };
+            search_statistics.elapsed = start.elapsed();
+            self.statistics_by_website
+                .insert(website.clone(), search_statistics);
+            results_by_website.insert(website, products);
+        }
         self.sort(&mut results_by_website);
+        if let Some(max_results) = self.parameters_processor.max_results() {
+            sorting::truncate(
+                &mut results_by_website,
+                self.parameters_processor.sort_by(),
+                max_results,
+            );
+        }
         results_by_website
     }
 
-    /// Sorts the products by the args.sort_by parameter
+    /// Returns the product of a url of a supported website.
+    /// The time spent is kept in the statistics.
     /// # Arguments
-    /// * `results_by_website` - The products for every shop.
-    fn sort(&self, results_by_website: &mut ResultsByWebsite) {
-        match self.parameters_processor.sorting_type() {
-            parameters::SortingType::Name => {
-                results_by_website
-                    .iter_mut()
-                    .for_each(|(_, results)| results.sort_by_key(|product| product.name.clone()));
-            }
-            parameters::SortingType::Price => {
-                //TODO: This is not working, we have to check the tones prices if we have no price in the product
-                results_by_website.iter_mut().for_each(|(_, results)| {
-                    results.sort_by(|p1, p2| {
-                        // If not found neither price_sales or price_standard, assign a f32::MAX to put it at the end of the vector.
-                        let price1 = if p1.price_sales.is_some() {
-                            p1.price_sales.unwrap()
-                        } else {
-                            p1.price_standard.unwrap_or(f32::MAX)
-                        };
-                        let price2 = if p2.price_sales.is_some() {
-                            p2.price_sales.unwrap()
-                        } else {
-                            p1.price_standard.unwrap_or(f32::MAX)
-                        };
-
-                        price2.partial_cmp(&price1).unwrap()
-                    })
-                });
-            }
-            parameters::SortingType::Similarity => {
-                results_by_website.iter_mut().for_each(|(_, results)| {
-                    results.sort_by(|p1, p2| p2.similarity.partial_cmp(&p1.similarity).unwrap())
-                })
-            }
-            parameters::SortingType::Brand => {
-                results_by_website
-                    .iter_mut()
-                    .for_each(|(_, results)| results.sort_by_key(|product| product.brand.clone()));
+    /// * `url` - The url of the product page.
+    /// # Returns
+    /// The product in its website, or an error if the url is not of a supported website or could not be retrieved.
+    pub fn look_up(&mut self, url: &str) -> Result<ResultsByWebsite, anyhow::Error> {
+        let website = parameters::Website::from_url(url)
+            .ok_or_else(|| anyhow::anyhow!("{url} is not a url of a supported website"))?;
+        self.statistics_by_website.clear();
+        let configuration = self.parameters_processor.configuration();
+
+        let start = Instant::now();
+        let product = match website {
+            parameters::Website::SephoraSpain => {
+                SephoraSpain::new(configuration).look_up_product(url)?
             }
-            parameters::SortingType::Rating => {
-                results_by_website.iter_mut().for_each(|(_, results)| {
-                    results.sort_by(|p1, p2| {
-                        let rating1 = p1.rating.unwrap_or(0_f32);
-                        let rating2 = p2.rating.unwrap_or(0_f32);
-                        rating2.partial_cmp(&rating1).unwrap()
-                    })
-                });
+            parameters::Website::Maquillalia => {
+                Maquillalia::new(configuration).look_up_product(url)?
             }
-        }
+            parameters::Website::All => unreachable!("All is not the website of any url"),
+        };
+        self.statistics_by_website.insert(
+            website.clone(),
+            SearchStatistics {
+                found: 1,
+                filtered_by_similarity: 0,
+                elapsed: start.elapsed(),
+            },
+        );
+        Ok(ResultsByWebsite::from([(website, vec![product])]))
+    }
+
+    /// Sorts the products of every website by the args.sort_by criteria.
+    /// # Arguments
+    /// * `results_by_website` - The products for every shop.
+    fn sort(&self, results_by_website: &mut ResultsByWebsite) {
+        let sort_by = self.parameters_processor.sort_by();
+        results_by_website.values_mut().for_each(|results| {
+            results.sort_by(|product1, product2| sorting::compare(product1, product2, sort_by))
+        });
     }
 
-    fn get_results_sephora_spain(&self) -> Vec<Product> {
+    fn get_results_sephora_spain(&self) -> (Vec<Product>, SearchStatistics) {
         let sephora_spain = SephoraSpain::new(self.parameters_processor.configuration());
-        let products =
-            match sephora_spain.look_for_products(self.parameters_processor.product().clone()) {
-                Ok(products) => products,
-                Err(err) => {
-                    eprintln!("{:?}", err);
-                    Vec::new()
-                }
-            };
-        products
+        self.search_query_variants(&sephora_spain)
     }
 
-    fn get_results_maquillalia(&self) -> Vec<Product> {
+    fn get_results_maquillalia(&self) -> (Vec<Product>, SearchStatistics) {
         let maquillalia = Maquillalia::new(self.parameters_processor.configuration());
-        let products =
-            match maquillalia.look_for_products(self.parameters_processor.product().clone()) {
-                Ok(products) => products,
-                Err(err) => {
-                    eprintln!("{:?}", err);
-                    Vec::new()
+        self.search_query_variants(&maquillalia)
+    }
+
+    /// Searches every query variant in the website and merges the products found.
+    /// # Arguments
+    /// * `website` - The website to search in.
+    /// # Returns
+    /// The products found that satisfy the query, without duplicated urls and scored against the best-matching variant,
+    /// and the number of products found and discarded for their similarity, without the time spent.
+    fn search_query_variants(&self, website: &impl Scrappable) -> (Vec<Product>, SearchStatistics) {
+        let query = self.parameters_processor.query();
+        let mut products: Vec<Product> = Vec::new();
+        // The urls of every result of every variant, to count each product found once.
+        let mut found: Vec<String> = Vec::new();
+        let mut last_error: Option<anyhow::Error> = None;
+        let mut any_results = false;
+
+        for variant in self.parameters_processor.query_variants() {
+            match website.search(query.search_text(&variant)) {
+                Ok(results) => {
+                    any_results = true;
+                    for product in results.similar {
+                        if !products.iter().any(|similar| similar.link == product.link) {
+                            products.push(product);
+                        }
+                    }
+                    for url in results.found {
+                        if !found.contains(&url) {
+                            found.push(url);
+                        }
+                    }
                 }
-            };
-        products
+                Err(err) => last_error = Some(err),
+            }
+        }
+        // We only report the error if none of the variants had results, even of other brands.
+        if !any_results {
+            if let Some(err) = last_error {
+                eprintln!("{:?}", err);
+            }
+        }
+
+        let products_count = products.len();
+        let products = self
+            .parameters_processor
+            .filters()
+            .filter(query.filter(products));
+        if products_count > 0 && products.is_empty() {
+            eprintln!(
+                "None of the {products_count} products found for \"{}\" matches the filters.",
+                self.parameters_processor.product()
+            );
+        }
+
+        let (products, below_min_similarity) = self.score(products);
+        let search_statistics = SearchStatistics {
+            found: found.len(),
+            filtered_by_similarity: found.len().saturating_sub(products_count)
+                + below_min_similarity,
+            ..Default::default()
+        };
+        (products, search_statistics)
+    }
+
+    /// Scores the products against the query and keeps the most similar ones.
+    /// The websites discard the products by their own similarity, but the query can score them
+    /// differently (e.g. only the name when it has a brand), so the minimum similarity is checked again.
+    /// # Arguments
+    /// * `products` - The products found in a website that satisfy the query and the filters.
+    /// # Returns
+    /// The products with at least the minimum similarity, from the most to the least similar,
+    /// up to the maximum of results of the website, and the number of products below the minimum similarity.
+    fn score(&self, mut products: Vec<Product>) -> (Vec<Product>, usize) {
+        let query = self.parameters_processor.query();
+        let configuration = self.parameters_processor.configuration();
+        for product in products.iter_mut() {
+            let explanation = query.explain_similarity(product);
+            product.similarity = explanation.score;
+            if self.parameters_processor.explain() {
+                product.similarity_explanation = Some(explanation);
+            }
+        }
+        let products_count = products.len();
+        products.retain(|product| product.similarity >= configuration.min_similarity());
+        let below_min_similarity = products_count - products.len();
+        // Every variant can retrieve up to the maximum of the website, keep only the best ones.
+        products.sort_by(|p1, p2| p2.similarity.total_cmp(&p1.similarity));
+        products.truncate(configuration.max_results());
+        (products, below_min_similarity)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::Config;
+    use crate::parameters::FilterArgs;
+    use scrapped_webs::product::Tone;
 
     /// Tests all the possible sorting.
     /// TODO: Improve this test.
@@ -161,58 +266,171 @@ mod tests {
     fn sort_all_paths() {
         let mut args = Args {
             product: String::from("labial"),
-            max_results: 2,
-            min_similarity: 0.0,
+            max_results: None,
+            max_results_per_site: Some(2),
+            min_similarity: Some(0.0),
             websites: vec![
                 parameters::Website::SephoraSpain,
                 parameters::Website::Maquillalia,
             ],
-            sort_by: parameters::SortingType::Similarity,
+            sort_by: Some(parameters::SortBy::from(
+                parameters::SortingType::Similarity,
+            )),
+            filters: FilterArgs::default(),
+            explain: false,
+            tui: false,
         };
-        let parameters_processor = ParametersProcessor::new(args.clone());
-        let scraper_handler = ScraperHandler::new(parameters_processor);
+        let parameters_processor =
+            ParametersProcessor::new(args.clone(), OutputArgs::default(), &Config::default());
+        let mut scraper_handler = ScraperHandler::new(parameters_processor);
         // Sort by Similarity
         scraper_handler.get_results();
         // Sort by Name
-        args.sort_by = parameters::SortingType::Name;
-        let parameters_processor = ParametersProcessor::new(args.clone());
-        let scraper_handler = ScraperHandler::new(parameters_processor);
+        args.sort_by = Some(parameters::SortBy::from(parameters::SortingType::Name));
+        let parameters_processor =
+            ParametersProcessor::new(args.clone(), OutputArgs::default(), &Config::default());
+        let mut scraper_handler = ScraperHandler::new(parameters_processor);
         scraper_handler.get_results();
         // Sort by Price
-        args.sort_by = parameters::SortingType::Price;
-        let parameters_processor = ParametersProcessor::new(args.clone());
-        let scraper_handler = ScraperHandler::new(parameters_processor);
+        args.sort_by = Some(parameters::SortBy::from(parameters::SortingType::Price));
+        let parameters_processor =
+            ParametersProcessor::new(args.clone(), OutputArgs::default(), &Config::default());
+        let mut scraper_handler = ScraperHandler::new(parameters_processor);
         scraper_handler.get_results();
         // Sort by Brand
-        args.sort_by = parameters::SortingType::Brand;
-        let parameters_processor = ParametersProcessor::new(args.clone());
-        let scraper_handler = ScraperHandler::new(parameters_processor);
+        args.sort_by = Some(parameters::SortBy::from(parameters::SortingType::Brand));
+        let parameters_processor =
+            ParametersProcessor::new(args.clone(), OutputArgs::default(), &Config::default());
+        let mut scraper_handler = ScraperHandler::new(parameters_processor);
         scraper_handler.get_results();
         // Sort by Rating
-        args.sort_by = parameters::SortingType::Rating;
-        let parameters_processor = ParametersProcessor::new(args.clone());
-        let scraper_handler = ScraperHandler::new(parameters_processor);
+        args.sort_by = Some(parameters::SortBy::from(parameters::SortingType::Rating));
+        let parameters_processor =
+            ParametersProcessor::new(args.clone(), OutputArgs::default(), &Config::default());
+        let mut scraper_handler = ScraperHandler::new(parameters_processor);
         scraper_handler.get_results();
     }
 
+    /// Tests the products scored below the minimum similarity by the query are discarded.
+    #[test]
+    fn score_min_similarity() {
+        let args = Args {
+            product: String::from("Rare Beauty colorete"),
+            max_results: None,
+            max_results_per_site: Some(50),
+            min_similarity: Some(0.8),
+            websites: vec![parameters::Website::SephoraSpain],
+            sort_by: None,
+            filters: FilterArgs::default(),
+            explain: false,
+            tui: false,
+        };
+        let scraper_handler = ScraperHandler::new(ParametersProcessor::new(
+            args,
+            OutputArgs::default(),
+            &Config::default(),
+        ));
+        let product = |name: &str| Product {
+            name: String::from(name),
+            brand: Some(String::from("Rare Beauty")),
+            ..Default::default()
+        };
+        // The websites score the brand and the name, but with a brand in the query only the name is scored.
+        let stick = product("Warm Wishes Effortless Bronzer Stick");
+        assert!(
+            scraper_handler
+                .parameters_processor()
+                .query()
+                .explain_similarity(&stick)
+                .score
+                < 0.8
+        );
+        let (scored, below_min_similarity) =
+            scraper_handler.score(vec![stick, product("Colorete")]);
+        assert_eq!(scored.len(), 1);
+        assert_eq!(below_min_similarity, 1);
+        assert_eq!(scored[0].name, "Colorete");
+        assert_eq!(scored[0].similarity, 1.0);
+    }
+
+    /// Tests the products are sorted by the price to pay, of their cheapest tone if they have tones.
+    #[test]
+    fn sort_by_price_with_tones() {
+        let args = Args {
+            product: String::from("labial"),
+            max_results: None,
+            max_results_per_site: Some(50),
+            min_similarity: Some(0.0),
+            websites: vec![parameters::Website::Maquillalia],
+            sort_by: Some(parameters::SortBy::from(parameters::SortingType::Price)),
+            filters: FilterArgs::default(),
+            explain: false,
+            tui: false,
+        };
+        let scraper_handler = ScraperHandler::new(ParametersProcessor::new(
+            args,
+            OutputArgs::default(),
+            &Config::default(),
+        ));
+        let product = |name: &str, price: Option<f32>, tones: Option<Vec<Tone>>| Product {
+            name: String::from(name),
+            price_standard: price,
+            tones,
+            ..Default::default()
+        };
+        let mut results_by_website = ResultsByWebsite::from([(
+            parameters::Website::Maquillalia,
+            vec![
+                product("Without price", None, None),
+                product("Expensive", Some(30.0), None),
+                product(
+                    "Cheap tone",
+                    Some(40.0),
+                    Some(vec![
+                        Tone::new(None, Some(25.0), Some(5.0), true, None, None),
+                        Tone::new(None, Some(20.0), None, true, None, None),
+                    ]),
+                ),
+                product("Cheap", Some(10.0), None),
+            ],
+        )]);
+        scraper_handler.sort(&mut results_by_website);
+        let names: Vec<&str> = results_by_website[&parameters::Website::Maquillalia]
+            .iter()
+            .map(|product| product.name.as_str())
+            .collect();
+        assert_eq!(
+            names,
+            vec!["Cheap tone", "Cheap", "Expensive", "Without price"]
+        );
+    }
+
     /// Tests a search for a product in two websites.
     #[test]
     #[ignore]
     fn get_results() {
         let args = Args {
             product: String::from("labial"),
-            max_results: 15,
-            min_similarity: 0.0,
+            max_results: None,
+            max_results_per_site: Some(15),
+            min_similarity: Some(0.0),
             websites: vec![
                 parameters::Website::SephoraSpain,
                 parameters::Website::Maquillalia,
             ],
-            sort_by: parameters::SortingType::Similarity,
+            sort_by: Some(parameters::SortBy::from(
+                parameters::SortingType::Similarity,
+            )),
+            filters: FilterArgs::default(),
+            explain: false,
+            tui: false,
         };
-        let parameters_processor = ParametersProcessor::new(args);
-        let scraper_handler = ScraperHandler::new(parameters_processor);
+        let parameters_processor =
+            ParametersProcessor::new(args, OutputArgs::default(), &Config::default());
+        let mut scraper_handler = ScraperHandler::new(parameters_processor);
         let results_by_website = scraper_handler.get_results();
         assert_eq!(results_by_website.len(), 2);
+        assert_eq!(scraper_handler.statistics_by_website().len(), 2);
     }
 
     /// Tests a search for a product in all websites.
@@ -221,13 +439,20 @@ mod tests {
     fn get_results_all_websites() {
         let args = Args {
             product: String::from("labial"),
-            max_results: 50,
-            min_similarity: 0.0,
+            max_results: None,
+            max_results_per_site: Some(50),
+            min_similarity: Some(0.0),
             websites: vec![parameters::Website::All],
-            sort_by: parameters::SortingType::Similarity,
+            sort_by: Some(parameters::SortBy::from(
+                parameters::SortingType::Similarity,
+            )),
+            filters: FilterArgs::default(),
+            explain: false,
+            tui: false,
         };
-        let parameters_processor = ParametersProcessor::new(args);
-        let scraper_handler = ScraperHandler::new(parameters_processor);
+        let parameters_processor =
+            ParametersProcessor::new(args, OutputArgs::default(), &Config::default());
+        let mut scraper_handler = ScraperHandler::new(parameters_processor);
         let _results_by_website = scraper_handler.get_results();
     }
 }
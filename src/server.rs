@@ -0,0 +1,240 @@
+//! HTTP server that returns the searches as JSON.
+
+use std::panic::{self, AssertUnwindSafe};
+
+use clap::{ArgEnum, CommandFactory, Parser};
+use tiny_http::{Header, Response, Server};
+use url::Url;
+
+use crate::config::Config;
+use crate::exporter;
+use crate::parameters::{Args, OutputArgs, OutputFormat, SearchRequest, Website};
+use crate::parameters_processor::ParametersProcessor;
+use crate::scraper_handler::{ResultsByWebsite, ScraperHandler};
+use crate::summary::Summary;
+
+/// Serves the searches until the process is stopped.
+/// # Arguments
+/// * `address` - The address to listen to, e.g. 127.0.0.1:8080.
+/// * `config` - The configuration of the searches, for the parameters not in the query string.
+/// # Returns
+/// An error if it is not possible to listen to the address.
+pub fn serve(address: &str, config: &Config) -> Result<(), anyhow::Error> {
+    let server = Server::http(address).map_err(|err| anyhow::anyhow!("{err}"))?;
+    eprintln!("Listening on http://{address}");
+    let content_type = Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..]).unwrap();
+    for request in server.incoming_requests() {
+        let (status, body) = respond(request.url(), config);
+        let response = Response::from_string(body)
+            .with_status_code(status)
+            .with_header(content_type.clone());
+        if let Err(err) = request.respond(response) {
+            eprintln!("Error responding: {:?}", err);
+        }
+    }
+    Ok(())
+}
+
+/// Returns the status and JSON body of a request.
+/// # Arguments
+/// * `target` - The path and query string requested, e.g. /search?product=labial&websites=maquillalia.
+/// * `config` - The configuration of the searches.
+/// # Example
+/// /sites = 200, [{"id": "sephora-spain", "name": "sephora.es"}, ...]
+/// /search = 400, {"error": "..."}
+fn respond(target: &str, config: &Config) -> (u16, String) {
+    let url = match Url::parse(&format!("http://localhost{target}")) {
+        Ok(url) => url,
+        Err(err) => return (400, error(&err.to_string())),
+    };
+    let parameters: Vec<(String, String)> = url.query_pairs().into_owned().collect();
+    match url.path() {
+        "/sites" => (200, sites()),
+        "/search" => guard(|| search(&parameters, config)),
+        "/lookup" => guard(|| lookup(&parameters, config)),
+        path => (404, error(&format!("{path} not found"))),
+    }
+}
+
+/// Returns the status and JSON body of a search, without stopping the server if scraping a website panics,
+/// e.g. because its pages have changed.
+/// # Returns
+/// 200 with the results, 400 if the request is not valid and 502 if a website could not be scraped.
+fn guard(search: impl FnOnce() -> Result<String, anyhow::Error>) -> (u16, String) {
+    match panic::catch_unwind(AssertUnwindSafe(search)) {
+        Ok(Ok(body)) => (200, body),
+        Ok(Err(err)) => (400, error(&err.to_string())),
+        Err(_) => (502, error("a website could not be scraped")),
+    }
+}
+
+/// Returns the supported websites, with the value to use in the websites parameter.
+fn sites() -> String {
+    let sites: Vec<serde_json::Value> = Website::value_variants()
+        .iter()
+        .filter(|website| **website != Website::All)
+        .map(|website| {
+            serde_json::json!({
+                "id": website.to_possible_value().unwrap().get_name(),
+                "name": website.to_string(),
+            })
+        })
+        .collect();
+    serde_json::Value::Array(sites).to_string()
+}
+
+/// Searches with the parameters of the query string, named as the options of the search subcommand.
+fn search(parameters: &[(String, String)], config: &Config) -> Result<String, anyhow::Error> {
+    let mut args = SearchRequest::try_parse_from(arguments(parameters))?.args;
+    args.tui = false;
+
+    let mut scraper_handler =
+        ScraperHandler::new(ParametersProcessor::new(args, json_output(), config));
+    let results_by_website = scraper_handler.get_results();
+    export(&results_by_website, &scraper_handler)
+}
+
+/// Returns the arguments of the search subcommand of the parameters of a query string.
+/// The flags are enabled with an empty value or true and disabled with false, e.g. on_sale=true,
+/// and the other options always take the value, e.g. product=false.
+fn arguments(parameters: &[(String, String)]) -> Vec<String> {
+    let command = SearchRequest::command();
+    let is_flag = |name: &str| {
+        command
+            .get_arguments()
+            .any(|arg| arg.get_long() == Some(name) && !arg.is_takes_value_set())
+    };
+    std::iter::once(String::from("search"))
+        .chain(parameters.iter().flat_map(|(name, value)| {
+            let long = name.replace('_', "-");
+            let option = format!("--{long}");
+            match value.as_str() {
+                "" | "true" if is_flag(&long) => vec![option],
+                "false" if is_flag(&long) => Vec::new(),
+                _ => vec![option, value.clone()],
+            }
+        }))
+        .collect()
+}
+
+/// Returns the product of the url parameter.
+fn lookup(parameters: &[(String, String)], config: &Config) -> Result<String, anyhow::Error> {
+    let url = parameters
+        .iter()
+        .find(|(name, _)| name == "url")
+        .map(|(_, url)| url)
+        .ok_or_else(|| anyhow::anyhow!("the url parameter is required"))?;
+    let mut scraper_handler = ScraperHandler::new(ParametersProcessor::new(
+        Args::look_up(url),
+        json_output(),
+        config,
+    ));
+    let results_by_website = scraper_handler.look_up(url)?;
+    export(&results_by_website, &scraper_handler)
+}
+
+/// Returns the output of the responses.
+fn json_output() -> OutputArgs {
+    OutputArgs {
+        output_format: Some(OutputFormat::Json),
+        plain: true,
+        ..Default::default()
+    }
+}
+
+/// Returns the results and summary of a search as JSON.
+fn export(
+    results_by_website: &ResultsByWebsite,
+    scraper_handler: &ScraperHandler,
+) -> Result<String, anyhow::Error> {
+    let summary = Summary::new(results_by_website, scraper_handler.statistics_by_website());
+    exporter::export(results_by_website, &summary, &OutputFormat::Json)
+}
+
+/// Returns the body of an error.
+fn error(message: &str) -> String {
+    serde_json::json!({ "error": message }).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests the supported websites are listed.
+    #[test]
+    fn respond_sites() {
+        let (status, body) = respond("/sites", &Config::default());
+        assert_eq!(status, 200);
+        let sites: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(sites[0]["id"], "sephora-spain");
+        assert_eq!(sites[0]["name"], "sephora.es");
+        assert_eq!(sites.as_array().unwrap().len(), 2);
+    }
+
+    /// Tests the values of the flags enable or disable them and the ones of the other options are kept.
+    #[test]
+    fn arguments_flags_and_values() {
+        let parameters = |pairs: &[(&str, &str)]| -> Vec<(String, String)> {
+            pairs
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect()
+        };
+        assert_eq!(
+            arguments(&parameters(&[
+                ("product", "false"),
+                ("on_sale", "true"),
+                ("explain", ""),
+                ("show_unavailable", "false"),
+                ("brand", "true"),
+            ])),
+            vec![
+                "search",
+                "--product",
+                "false",
+                "--on-sale",
+                "--explain",
+                "--brand",
+                "true"
+            ]
+        );
+    }
+
+    /// Tests a panic scraping a website is answered with an error instead of stopping the server.
+    #[test]
+    fn guard_panics() {
+        assert_eq!(guard(|| Ok(String::from("[]"))), (200, String::from("[]")));
+        assert_eq!(guard(|| Err(anyhow::anyhow!("not valid"))).0, 400);
+        let (status, body) = guard(|| panic!("no price in the page"));
+        assert_eq!(status, 502);
+        assert!(body.contains("a website could not be scraped"));
+    }
+
+    /// Tests the errors of the requests that can not be answered.
+    #[test]
+    fn respond_errors() {
+        assert_eq!(respond("/products", &Config::default()).0, 404);
+
+        let (status, body) = respond("/search?max_results=10", &Config::default());
+        assert_eq!(status, 400);
+        assert!(body.contains("--product"));
+
+        let (status, body) = respond("/search?product=labial&sort_by=colour", &Config::default());
+        assert_eq!(status, 400);
+        assert!(body.contains("colour"));
+
+        assert_eq!(
+            respond("/lookup", &Config::default()),
+            (
+                400,
+                String::from(r#"{"error":"the url parameter is required"}"#)
+            )
+        );
+        let (status, body) = respond(
+            "/lookup?url=https%3A%2F%2Fwww.example.com%2Fp%2F1",
+            &Config::default(),
+        );
+        assert_eq!(status, 400);
+        assert!(body.contains("is not a url of a supported website"));
+    }
+}
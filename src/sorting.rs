@@ -0,0 +1,317 @@
+//! Sorting of the products by several criteria, the same in every website and in all of them merged.
+
+use std::cmp::Ordering;
+use std::collections::HashSet;
+
+use scrapped_webs::product::Product;
+use scrapped_webs::similarity;
+
+use crate::parameters::{SortBy, SortKey, SortOrder, SortingType, Website};
+use crate::scraper_handler::ResultsByWebsite;
+
+/// Compares two products by the sorting criteria, from the most to the least important.
+/// The products without the value of a criterion (price, brand or rating) go after the ones with it in both directions.
+/// Ties are broken by name, brand and link, so the order does not depend on the order the products were found.
+/// # Arguments
+/// * `product1` - The first product.
+/// * `product2` - The second product.
+/// * `sort_by` - The sorting criteria.
+pub fn compare(product1: &Product, product2: &Product, sort_by: &SortBy) -> Ordering {
+    sort_by
+        .0
+        .iter()
+        .chain([
+            &SortKey::new(SortingType::Name),
+            &SortKey::new(SortingType::Brand),
+        ])
+        .fold(Ordering::Equal, |ordering, key| {
+            ordering.then_with(|| compare_key(product1, product2, key))
+        })
+        .then_with(|| product1.link.cmp(&product2.link))
+}
+
+/// Returns the products of every website in a single list sorted by the criteria.
+/// The same product in several websites is sorted by website.
+/// # Arguments
+/// * `results_by_website` - The products found for every website.
+/// * `sort_by` - The sorting criteria.
+pub fn merge<'a>(
+    results_by_website: &'a ResultsByWebsite,
+    sort_by: &SortBy,
+) -> Vec<(&'a Website, &'a Product)> {
+    rank(results_by_website, sort_by)
+        .into_iter()
+        .map(|(website, _, product)| (website, product))
+        .collect()
+}
+
+/// Keeps only the first products of all the websites merged and sorted by the criteria,
+/// removing the rest from their websites. The order of the products of every website is kept.
+/// # Arguments
+/// * `results_by_website` - The products found for every website.
+/// * `sort_by` - The sorting criteria.
+/// * `max_results` - The number of products to keep in all the websites together.
+pub fn truncate(results_by_website: &mut ResultsByWebsite, sort_by: &SortBy, max_results: usize) {
+    let kept: HashSet<(Website, usize)> = rank(results_by_website, sort_by)
+        .into_iter()
+        .take(max_results)
+        .map(|(website, index, _)| (website.clone(), index))
+        .collect();
+    for (website, products) in results_by_website.iter_mut() {
+        let mut index = 0;
+        products.retain(|_| {
+            index += 1;
+            kept.contains(&(website.clone(), index - 1))
+        });
+    }
+}
+
+/// Returns the products of every website with their position in it, in a single list sorted by the criteria.
+fn rank<'a>(
+    results_by_website: &'a ResultsByWebsite,
+    sort_by: &SortBy,
+) -> Vec<(&'a Website, usize, &'a Product)> {
+    let mut products: Vec<(&Website, usize, &Product)> = results_by_website
+        .iter()
+        .flat_map(|(website, products)| {
+            products
+                .iter()
+                .enumerate()
+                .map(move |(index, product)| (website, index, product))
+        })
+        .collect();
+    products.sort_by(|(website1, _, product1), (website2, _, product2)| {
+        compare(product1, product2, sort_by).then_with(|| website1.cmp(website2))
+    });
+    products
+}
+
+/// Compares two products by a single criterion.
+fn compare_key(product1: &Product, product2: &Product, key: &SortKey) -> Ordering {
+    let text = |text: &str| Some(similarity::normalize(text)).filter(|text| !text.is_empty());
+    let brand = |product: &Product| product.brand.as_deref().and_then(text);
+    match key.sorting_type {
+        SortingType::Name => compare_values(
+            text(&product1.name),
+            text(&product2.name),
+            key.order,
+            Ord::cmp,
+        ),
+        SortingType::Brand => compare_values(brand(product1), brand(product2), key.order, Ord::cmp),
+        SortingType::Price => compare_values(
+            product1.effective_price(),
+            product2.effective_price(),
+            key.order,
+            f32::total_cmp,
+        ),
+        SortingType::Similarity => compare_values(
+            Some(product1.similarity),
+            Some(product2.similarity),
+            key.order,
+            f32::total_cmp,
+        ),
+        SortingType::Rating => compare_values(
+            product1.effective_rating(),
+            product2.effective_rating(),
+            key.order,
+            f32::total_cmp,
+        ),
+    }
+}
+
+/// Compares two values in the order requested, with the missing values always at the end.
+pub fn compare_values<T>(
+    value1: Option<T>,
+    value2: Option<T>,
+    order: SortOrder,
+    cmp: impl Fn(&T, &T) -> Ordering,
+) -> Ordering {
+    match (value1, value2) {
+        (Some(value1), Some(value2)) => match order {
+            SortOrder::Asc => cmp(&value1, &value2),
+            SortOrder::Desc => cmp(&value2, &value1),
+        },
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use scrapped_webs::product::Tone;
+
+    use super::*;
+
+    /// Creates a product for the tests.
+    fn product(
+        name: &str,
+        brand: Option<&str>,
+        price: Option<f32>,
+        rating: Option<f32>,
+    ) -> Product {
+        Product {
+            name: String::from(name),
+            brand: brand.map(String::from),
+            link: format!("https://www.test.com/{name}"),
+            price_standard: price,
+            rating,
+            ..Default::default()
+        }
+    }
+
+    /// Returns the names of the products sorted.
+    fn sorted(mut products: Vec<Product>, sort_by: &str) -> Vec<String> {
+        let sort_by = SortBy::from_str(sort_by).unwrap();
+        products.sort_by(|product1, product2| compare(product1, product2, &sort_by));
+        products.into_iter().map(|product| product.name).collect()
+    }
+
+    /// Tests the products are sorted by several criteria, with the missing values at the end in both directions.
+    #[test]
+    fn compare_several_keys() {
+        let products = vec![
+            product("Gloss", Some("NYX"), Some(10.0), Some(4.0)),
+            product("Blush", Some("Milani"), None, Some(4.5)),
+            product("Lipstick", None, Some(10.0), Some(4.8)),
+            product("Mascara", Some("Essence"), Some(5.0), None),
+        ];
+        assert_eq!(
+            sorted(products.clone(), "price:asc,rating:desc"),
+            vec!["Mascara", "Lipstick", "Gloss", "Blush"]
+        );
+        assert_eq!(
+            sorted(products.clone(), "price:desc,rating:asc"),
+            vec!["Gloss", "Lipstick", "Mascara", "Blush"]
+        );
+        assert_eq!(
+            sorted(products.clone(), "rating"),
+            vec!["Lipstick", "Blush", "Gloss", "Mascara"]
+        );
+        assert_eq!(
+            sorted(products.clone(), "brand:desc"),
+            vec!["Gloss", "Blush", "Mascara", "Lipstick"]
+        );
+        assert_eq!(
+            sorted(products, "name"),
+            vec!["Blush", "Gloss", "Lipstick", "Mascara"]
+        );
+    }
+
+    /// Tests the products without rating of their own are sorted by the best rating of their tones.
+    #[test]
+    fn compare_rating_of_tones() {
+        let mut blush = product("Blush", None, Some(10.0), None);
+        blush.tones = Some(vec![
+            Tone::new(None, None, None, true, None, Some(4.2)),
+            Tone::new(None, None, None, true, None, Some(4.9)),
+        ]);
+        let products = vec![
+            product("Gloss", None, Some(10.0), Some(4.5)),
+            blush,
+            product("Mascara", None, Some(5.0), None),
+        ];
+        assert_eq!(
+            sorted(products.clone(), "rating"),
+            vec!["Blush", "Gloss", "Mascara"]
+        );
+        assert_eq!(
+            sorted(products, "rating:asc"),
+            vec!["Gloss", "Blush", "Mascara"]
+        );
+    }
+
+    /// Tests the ties are broken by name, brand and link, whatever the order of the products.
+    #[test]
+    fn compare_ties() {
+        let mut products = vec![
+            product("gloss", Some("NYX"), Some(10.0), None),
+            product("Blush", Some("Milani"), Some(10.0), None),
+            product("Gloss", Some("Milani"), Some(10.0), None),
+        ];
+        products[0].link = String::from("https://www.test.com/b");
+        let mut reversed = products.clone();
+        reversed.reverse();
+        assert_eq!(sorted(products.clone(), "price"), sorted(reversed, "price"));
+        assert_eq!(sorted(products, "price"), vec!["Blush", "Gloss", "gloss"]);
+    }
+
+    /// Tests the products of every website are merged in a single list.
+    #[test]
+    fn merge_websites() {
+        let results_by_website = ResultsByWebsite::from([
+            (
+                Website::SephoraSpain,
+                vec![
+                    product("Gloss", Some("NYX"), Some(12.0), None),
+                    product("Blush", Some("NYX"), Some(20.0), None),
+                ],
+            ),
+            (
+                Website::Maquillalia,
+                vec![
+                    product("Gloss", Some("NYX"), Some(12.0), None),
+                    product("Mascara", Some("NYX"), Some(5.0), None),
+                ],
+            ),
+        ]);
+        let merged: Vec<(&Website, &str)> =
+            merge(&results_by_website, &SortBy::from(SortingType::Price))
+                .into_iter()
+                .map(|(website, product)| (website, product.name.as_str()))
+                .collect();
+        assert_eq!(
+            merged,
+            vec![
+                (&Website::Maquillalia, "Mascara"),
+                (&Website::SephoraSpain, "Gloss"),
+                (&Website::Maquillalia, "Gloss"),
+                (&Website::SephoraSpain, "Blush"),
+            ]
+        );
+    }
+
+    /// Tests only the first products of all the websites together are kept, whatever website they are from.
+    #[test]
+    fn truncate_websites() {
+        let results_by_website = ResultsByWebsite::from([
+            (
+                Website::SephoraSpain,
+                vec![
+                    product("Blush", Some("NYX"), Some(20.0), None),
+                    product("Gloss", Some("NYX"), Some(12.0), None),
+                ],
+            ),
+            (
+                Website::Maquillalia,
+                vec![
+                    product("Mascara", Some("NYX"), Some(5.0), None),
+                    product("Gloss", Some("NYX"), Some(12.0), None),
+                    product("Primer", Some("NYX"), None, None),
+                ],
+            ),
+        ]);
+        let names = |results_by_website: &ResultsByWebsite, website: &Website| -> Vec<String> {
+            results_by_website[website]
+                .iter()
+                .map(|product| product.name.clone())
+                .collect()
+        };
+        let sort_by = SortBy::from(SortingType::Price);
+
+        let mut truncated = results_by_website.clone();
+        truncate(&mut truncated, &sort_by, 2);
+        assert_eq!(names(&truncated, &Website::SephoraSpain), vec!["Gloss"]);
+        assert_eq!(names(&truncated, &Website::Maquillalia), vec!["Mascara"]);
+
+        let mut truncated = results_by_website.clone();
+        truncate(&mut truncated, &sort_by, 0);
+        assert!(truncated.values().all(Vec::is_empty));
+
+        let mut truncated = results_by_website.clone();
+        truncate(&mut truncated, &sort_by, 10);
+        assert_eq!(truncated, results_by_website);
+    }
+}
@@ -0,0 +1,271 @@
+//! Summary statistics of a search, for every website and overall.
+
+use std::collections::BTreeMap;
+
+use scrapped_webs::product::{Product, Tone};
+use serde::Serialize;
+
+use crate::parameters::Website;
+use crate::scraper_handler::{ResultsByWebsite, SearchStatistics, StatisticsByWebsite};
+
+/// Statistics of the products found in a website, or in all of them.
+/// The prices are of every tone, or of the product if it has no tones.
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct Statistics {
+    /// Products found in the search of the website, of the brands accepted.
+    pub found: usize,
+    /// Products found discarded for being below the minimum similarity.
+    pub filtered_by_similarity: usize,
+    /// Products in the results.
+    pub shown: usize,
+    /// The lowest price to pay.
+    pub cheapest: Option<f32>,
+    /// The highest price to pay.
+    pub most_expensive: Option<f32>,
+    /// The average price to pay.
+    pub average_price: Option<f32>,
+    /// Number of tones on sale.
+    pub discounts: usize,
+    /// Average percentage of discount of the tones on sale.
+    pub average_discount: Option<f32>,
+    /// Number of tones not available.
+    pub unavailable_tones: usize,
+    /// Number of products without tones not available.
+    pub unavailable_products: usize,
+    /// Time spent searching, in seconds.
+    pub time_seconds: f64,
+}
+
+impl Statistics {
+    /// Computes the statistics of some products.
+    /// # Arguments
+    /// * `products` - The products in the results.
+    /// * `search_statistics` - The products found and the time spent searching them.
+    /// # Returns
+    /// The statistics, without prices or discounts if there are none.
+    fn new<'a>(
+        products: impl Iterator<Item = &'a Product>,
+        search_statistics: SearchStatistics,
+    ) -> Self {
+        let mut statistics = Self {
+            found: search_statistics.found,
+            filtered_by_similarity: search_statistics.filtered_by_similarity,
+            time_seconds: search_statistics.elapsed.as_secs_f64(),
+            ..Default::default()
+        };
+        let mut prices: Vec<f32> = Vec::new();
+        let mut discounts: Vec<u8> = Vec::new();
+        for product in products {
+            statistics.shown += 1;
+            match product.tones.as_ref() {
+                Some(tones) => {
+                    prices.extend(tones.iter().filter_map(Tone::price));
+                    discounts.extend(tones.iter().filter_map(Tone::discount_percentage));
+                    statistics.unavailable_tones +=
+                        tones.iter().filter(|tone| !tone.available).count();
+                }
+                None => {
+                    prices.extend(product.price());
+                    discounts.extend(product.discount_percentage());
+                    if !product.available {
+                        statistics.unavailable_products += 1;
+                    }
+                }
+            }
+        }
+        statistics.cheapest = prices.iter().copied().reduce(f32::min);
+        statistics.most_expensive = prices.iter().copied().reduce(f32::max);
+        statistics.average_price =
+            (!prices.is_empty()).then(|| prices.iter().sum::<f32>() / prices.len() as f32);
+        statistics.discounts = discounts.len();
+        statistics.average_discount = (!discounts.is_empty()).then(|| {
+            discounts
+                .iter()
+                .map(|discount| *discount as f32)
+                .sum::<f32>()
+                / discounts.len() as f32
+        });
+        statistics
+    }
+}
+
+/// Statistics of a search for every website and overall.
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct Summary {
+    /// The statistics of every website searched.
+    pub websites: BTreeMap<Website, Statistics>,
+    /// The statistics of all the websites together.
+    pub overall: Statistics,
+}
+
+impl Summary {
+    /// Computes the summary of a search.
+    /// # Arguments
+    /// * `results_by_website` - The products found for every website.
+    /// * `statistics_by_website` - The products found and the time spent in every website.
+    /// # Returns
+    /// The summary, with a website for every website searched.
+    pub fn new(
+        results_by_website: &ResultsByWebsite,
+        statistics_by_website: &StatisticsByWebsite,
+    ) -> Self {
+        let mut websites: Vec<&Website> = results_by_website
+            .keys()
+            .chain(statistics_by_website.keys())
+            .collect();
+        websites.sort();
+        websites.dedup();
+
+        let search_statistics = |website: &Website| {
+            statistics_by_website
+                .get(website)
+                .copied()
+                .unwrap_or_default()
+        };
+        let products = |website: &Website| results_by_website.get(website).into_iter().flatten();
+        let overall_search_statistics = SearchStatistics {
+            found: websites
+                .iter()
+                .map(|website| search_statistics(website).found)
+                .sum(),
+            filtered_by_similarity: websites
+                .iter()
+                .map(|website| search_statistics(website).filtered_by_similarity)
+                .sum(),
+            elapsed: websites
+                .iter()
+                .map(|website| search_statistics(website).elapsed)
+                .sum(),
+        };
+        Self {
+            websites: websites
+                .iter()
+                .map(|website| {
+                    (
+                        (*website).clone(),
+                        Statistics::new(products(website), search_statistics(website)),
+                    )
+                })
+                .collect(),
+            overall: Statistics::new(
+                websites.iter().flat_map(|website| products(website)),
+                overall_search_statistics,
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    /// Creates the results used in the tests, with tones in sephora.es and without them in maquillalia.com.
+    fn tear_up() -> (ResultsByWebsite, StatisticsByWebsite) {
+        let sephora_product = Product::new(
+            String::from("Soft Pinch Liquid Blush"),
+            Some(String::from("Rare Beauty")),
+            String::from("https://www.sephora.es/1"),
+            None,
+            None,
+            Some(vec![
+                Tone::new(None, Some(20.0), Some(15.0), true, None, None),
+                Tone::new(None, Some(30.0), None, false, None, None),
+                Tone::new(None, None, None, false, None, None),
+            ]),
+            None,
+            0.92,
+            true,
+        );
+        let maquillalia_product = Product::new(
+            String::from("Butter Gloss"),
+            Some(String::from("NYX")),
+            String::from("https://www.maquillalia.com/1"),
+            Some(10.0),
+            Some(5.0),
+            None,
+            None,
+            0.9,
+            false,
+        );
+        let mut results_by_website = ResultsByWebsite::new();
+        results_by_website.insert(Website::SephoraSpain, vec![sephora_product]);
+        results_by_website.insert(Website::Maquillalia, vec![maquillalia_product]);
+        let mut statistics_by_website = StatisticsByWebsite::new();
+        statistics_by_website.insert(
+            Website::SephoraSpain,
+            SearchStatistics {
+                found: 4,
+                filtered_by_similarity: 2,
+                elapsed: Duration::from_millis(1500),
+            },
+        );
+        statistics_by_website.insert(
+            Website::Maquillalia,
+            SearchStatistics {
+                found: 1,
+                filtered_by_similarity: 0,
+                elapsed: Duration::from_millis(500),
+            },
+        );
+        (results_by_website, statistics_by_website)
+    }
+
+    /// Tests the statistics of every website and overall.
+    #[test]
+    fn new_happy_path() {
+        let (results_by_website, statistics_by_website) = tear_up();
+        let summary = Summary::new(&results_by_website, &statistics_by_website);
+        assert_eq!(
+            summary.websites[&Website::SephoraSpain],
+            Statistics {
+                found: 4,
+                filtered_by_similarity: 2,
+                shown: 1,
+                cheapest: Some(15.0),
+                most_expensive: Some(30.0),
+                average_price: Some(22.5),
+                discounts: 1,
+                average_discount: Some(25.0),
+                unavailable_tones: 2,
+                unavailable_products: 0,
+                time_seconds: 1.5,
+            }
+        );
+        assert_eq!(summary.websites[&Website::Maquillalia].unavailable_tones, 0);
+        assert_eq!(
+            summary.websites[&Website::Maquillalia].unavailable_products,
+            1
+        );
+        assert_eq!(
+            summary.overall,
+            Statistics {
+                found: 5,
+                filtered_by_similarity: 2,
+                shown: 2,
+                cheapest: Some(5.0),
+                most_expensive: Some(30.0),
+                average_price: Some(50.0 / 3.0),
+                discounts: 2,
+                average_discount: Some(37.5),
+                unavailable_tones: 2,
+                unavailable_products: 1,
+                time_seconds: 2.0,
+            }
+        );
+    }
+
+    /// Tests the websites without products have statistics without prices.
+    #[test]
+    fn new_without_products() {
+        let (_, statistics_by_website) = tear_up();
+        let summary = Summary::new(&ResultsByWebsite::new(), &statistics_by_website);
+        assert_eq!(summary.websites.len(), 2);
+        assert_eq!(summary.overall.found, 5);
+        assert_eq!(summary.overall.filtered_by_similarity, 2);
+        assert_eq!(summary.overall.shown, 0);
+        assert_eq!(summary.overall.cheapest, None);
+        assert_eq!(summary.overall.average_discount, None);
+    }
+}
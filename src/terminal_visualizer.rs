@@ -1,44 +1,738 @@
 //! This file handles the visualization in the terminal.
 
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
-use scrapped_webs::product::Product;
+use ansi_term::{Colour, Style};
+use scrapped_webs::brands;
+use scrapped_webs::colour::ColourMode;
+use scrapped_webs::locale::{Label, Locale};
+use scrapped_webs::product::{Product, Tone, MISSING_FIELD};
+use scrapped_webs::similarity;
+use unicode_width::UnicodeWidthStr;
 
-use crate::parameters;
+use crate::parameters::{SortBy, Website};
+use crate::scraper_handler::ResultsByWebsite;
+use crate::sorting;
+use crate::summary::{Statistics, Summary};
 
-type ResultsByWebsite = HashMap<parameters::Website, Vec<Product>>;
+/// Minimum similarity between two product names to be shown in the same row of the table.
+const SAME_PRODUCT_SIMILARITY: f32 = 0.9;
+/// Width used when the width of the terminal can not be known, e.g. when the output is piped.
+const DEFAULT_TERMINAL_WIDTH: usize = 120;
+/// Minimum width of the product column before the cells are shortened.
+const MIN_PRODUCT_WIDTH: usize = 20;
+/// Separator between the columns of the table.
+const COLUMN_SEPARATOR: &str = " │ ";
+/// Separator between the columns of the table in plain mode.
+const PLAIN_COLUMN_SEPARATOR: &str = " | ";
 
-/// Prints the formatted output in the terminal
+/// Returns the formatted output to be printed in the terminal
 /// # Example
-/// 95%. Labial Rare Beauty - 9.99 - 7.5⭐ - www.sephora.es ///
-///     - ✔️ Tone1 ̶- 9̶.̶9̶9̶  4.99(50%) - 9.5⭐               ///
-///     - ❌ Tone2 - 9.99                                  ///
-/// 72%. Colorete Sephora - 9.99 - 7.5⭐ - www.sephora.es ///
-///     - ✔️ Tone1 ̶- 9̶.̶9̶9̶  4.99(50%) - 9.5⭐             ///
-pub fn print(results_by_website: &ResultsByWebsite) {
-    // Right now we are not using the website to print since the results are already sorted and filtered.
-    for product in results_by_website.values().flatten() {
-        println!();
-        println!("{}", product.terminal_format());
+/// - 92.00%. Soft Pinch Liquid Blush - Rare Beauty - €20.24 - €26.99 - 9.5⭐: https://www.sephora.es/p/1
+///     - ✔️ Joy - ̶€̶2̶6̶.̶9̶9̶ €20.24(25%) - 9.5⭐
+///     - ❌ Hope - €24.95
+/// - 72.00%. Colorete - Sephora Collection - €9.99 - 7.5⭐: https://www.maquillalia.com/p/2
+///
+/// The tones with a colour are shown with a swatch painted in the colour mode provided.
+/// In plain mode there are no colours, emoji or escape sequences: ASCII markers, symbols and sale prices,
+/// with the euro sign of the locale in the prices.
+/// The labels and prices follow the locale provided.
+/// With `dim_unavailable` the products and tones that can not be bought are dimmed, except in plain mode.
+/// The products of every website are printed in a single list sorted by the criteria provided.
+pub fn format(
+    results_by_website: &ResultsByWebsite,
+    sort_by: &SortBy,
+    colour_mode: ColourMode,
+    locale: Locale,
+    dim_unavailable: bool,
+) -> String {
+    let dim = |line: String, available: bool| match dim_unavailable
+        && !available
+        && colour_mode != ColourMode::Plain
+    {
+        true => Style::new().dimmed().paint(line).to_string(),
+        false => line,
+    };
+    let mut out: String = String::new();
+    // The website is already in the link of every product.
+    for (_, product) in sorting::merge(results_by_website, sort_by) {
+        out.push('\n');
+        let line = product.terminal_format(colour_mode, locale);
+        out.push_str(format!("{}\n", dim(line, Offer::new(product).available)).as_str());
+        if let Some(explanation) = product.similarity_explanation.as_ref() {
+            out.push_str(format!("{}\n", explanation.terminal_format(colour_mode)).as_str());
+        }
         if let Some(tones) = product.tones.as_ref() {
             for tone in tones {
-                println!("{}", tone.terminal_format());
+                let line = tone.terminal_format(colour_mode, locale);
+                out.push_str(format!("{}\n", dim(line, tone.available)).as_str());
             }
         };
     }
+    out
+}
+
+/// Price and availability of a product in a website, a cell of the table.
+#[derive(Debug, PartialEq)]
+pub struct Offer {
+    /// The standard price, of the cheapest tone if the product has tones.
+    pub price_standard: Option<f32>,
+    /// The price on sale, of the cheapest tone if the product has tones.
+    pub price_sales: Option<f32>,
+    /// The percentage of discount if it is on sale.
+    pub discount_percentage: Option<u8>,
+    /// True if the product or any of its tones is available.
+    pub available: bool,
+}
+
+impl Offer {
+    /// Creates the offer of a product, using its cheapest tone if it has tones:
+    /// the one with the effective price of the product.
+    pub fn new(product: &Product) -> Self {
+        match product.tones.as_ref() {
+            Some(tones) => {
+                let price = product.effective_price();
+                let cheapest_tone = tones
+                    .iter()
+                    .find(|tone| tone.price().is_some() && tone.price() == price);
+                Self {
+                    price_standard: cheapest_tone.and_then(|tone| tone.price_standard),
+                    price_sales: cheapest_tone.and_then(|tone| tone.price_sales),
+                    discount_percentage: cheapest_tone.and_then(|tone| tone.discount_percentage()),
+                    available: tones.iter().any(|tone| tone.available),
+                }
+            }
+            None => Self {
+                price_standard: product.price_standard,
+                price_sales: product.price_sales,
+                discount_percentage: product.discount_percentage(),
+                available: product.available,
+            },
+        }
+    }
+
+    /// Returns the price to pay, the price on sale if it is on sale.
+    pub fn price(&self) -> Option<f32> {
+        self.price_sales.or(self.price_standard)
+    }
+
+    /// Formats the prices in the format of the locale, the way of every output format.
+    /// # Arguments
+    /// * `locale` - The format of the prices, e.g. €8.99 or 8,99 €.
+    /// * `on_sale` - Writes the standard price, the price on sale and the discount, e.g. ~~€26.99~~ €20.24 (-25%) in Markdown.
+    /// * `price` - Writes the price to pay when it is not on sale, e.g. €8.99.
+    /// # Returns
+    /// The price written, or N/A if there is no price.
+    pub fn price_format(
+        &self,
+        locale: Locale,
+        on_sale: impl Fn(&str, &str, u8) -> String,
+        price: impl Fn(&str) -> String,
+    ) -> String {
+        match (
+            self.price_standard,
+            self.price_sales,
+            self.discount_percentage,
+        ) {
+            (Some(price_standard), Some(price_sales), Some(discount_percentage)) => on_sale(
+                &locale.price(price_standard),
+                &locale.price(price_sales),
+                discount_percentage,
+            ),
+            (_, Some(price_to_pay), _) | (Some(price_to_pay), None, _) => {
+                price(&locale.price(price_to_pay))
+            }
+            (None, None, _) => String::from(MISSING_FIELD),
+        }
+    }
+
+    /// Formats the offer to be shown in a cell, with the prices in the format of the locale.
+    /// # Example
+    /// full: ✔ €19.99 → €14.99 -25%
+    /// compact: ✔ €14.99
+    /// plain: yes €19.99 -> €14.99 -25%
+    fn cell_format(&self, compact: bool, colour_mode: ColourMode, locale: Locale) -> String {
+        let plain = colour_mode == ColourMode::Plain;
+        let availability = match (plain, self.available) {
+            (true, available) => {
+                let (yes, no) = (locale.label(Label::Yes), locale.label(Label::No));
+                let width = yes.width().max(no.width());
+                pad(if available { yes } else { no }, width)
+            }
+            (false, available) => String::from(availability_mark(available)),
+        };
+        let arrow = if plain { "->" } else { "→" };
+        match (self.price(), self.discount_percentage) {
+            (None, _) => format!("{availability} {MISSING_FIELD}"),
+            (Some(price), Some(discount)) if !compact => format!(
+                "{availability} {} {arrow} {} -{discount}%",
+                locale.price(self.price_standard.unwrap_or(price)),
+                locale.price(price)
+            ),
+            (Some(price), _) => format!("{availability} {}", locale.price(price)),
+        }
+    }
+}
+
+impl From<&Tone> for Offer {
+    /// Creates the offer of a single tone.
+    fn from(tone: &Tone) -> Self {
+        Self {
+            price_standard: tone.price_standard,
+            price_sales: tone.price_sales,
+            discount_percentage: tone.discount_percentage(),
+            available: tone.available,
+        }
+    }
+}
+
+/// Returns the mark of the availability of a product or tone.
+pub fn availability_mark(available: bool) -> &'static str {
+    match available {
+        true => "✔",
+        false => "✘",
+    }
+}
+
+/// Row of the table, the same product found in different websites.
+pub struct Row<'a> {
+    /// The brand and name of the first product found.
+    pub name: String,
+    /// The product found in every website.
+    pub products: BTreeMap<&'a Website, &'a Product>,
+}
+
+impl<'a> Row<'a> {
+    /// Returns true if the product is the same as the one of the row.
+    fn matches(&self, product: &Product) -> bool {
+        let first_product = self.products.values().next().unwrap();
+        let same_brand = match (first_product.brand.as_ref(), product.brand.as_ref()) {
+            (Some(brand1), Some(brand2)) => brands::same_brand(brand1, brand2),
+            _ => true,
+        };
+        same_brand
+            && similarity::best_similarity(
+                &similarity::normalize(&product.name),
+                &[similarity::normalize(&first_product.name)],
+            ) >= SAME_PRODUCT_SIMILARITY
+    }
+}
+
+/// Groups the same product found in different websites.
+/// # Arguments
+/// * `results_by_website` - The products found for every website.
+/// # Returns
+/// The websites with products, sorted, and a row for every different product in the order they were found.
+pub fn group_products(results_by_website: &ResultsByWebsite) -> (Vec<&Website>, Vec<Row<'_>>) {
+    let mut websites: Vec<&Website> = results_by_website
+        .iter()
+        .filter(|(_, products)| !products.is_empty())
+        .map(|(website, _)| website)
+        .collect();
+    websites.sort();
+
+    let mut rows: Vec<Row> = Vec::new();
+    for website in websites.iter() {
+        for product in results_by_website[*website].iter() {
+            match rows
+                .iter_mut()
+                .find(|row| !row.products.contains_key(website) && row.matches(product))
+            {
+                Some(row) => {
+                    row.products.insert(website, product);
+                }
+                None => rows.push(Row {
+                    name: match product.brand.as_ref() {
+                        Some(brand) => format!("{} {}", brand.trim(), product.name.trim()),
+                        None => product.name.trim().to_string(),
+                    },
+                    products: BTreeMap::from([(*website, product)]),
+                }),
+            }
+        }
+    }
+    (websites, rows)
+}
+
+/// Returns the width of the terminal or a default width if it is not a terminal.
+pub fn terminal_width() -> usize {
+    terminal_size::terminal_size()
+        .map(|(terminal_size::Width(width), _)| width as usize)
+        .unwrap_or(DEFAULT_TERMINAL_WIDTH)
+}
+
+/// Returns the results as a table with the same product in every row and a column for every website.
+/// The lowest price of every row is highlighted, or marked with "(best)" in plain mode.
+/// # Arguments
+/// * `results_by_website` - The products found for every website.
+/// * `width` - The width of the terminal, the prices are shortened and the names truncated to fit in.
+/// * `colour_mode` - Plain mode draws the table only with ASCII and without escape sequences.
+/// * `locale` - The language of the labels and the format of the prices.
+/// # Example
+/// Product                      │ sephora.es               │ maquillalia.com
+/// ─────────────────────────────┼──────────────────────────┼────────────────
+/// Rare Beauty Soft Pinch Blush │ ✔ €26.99 → €20.24 -25%   │ ✘ €24.95
+/// NYX Butter Gloss             │ ✔ €8.99                  │
+pub fn format_table(
+    results_by_website: &ResultsByWebsite,
+    width: usize,
+    colour_mode: ColourMode,
+    locale: Locale,
+) -> String {
+    let (websites, rows) = group_products(results_by_website);
+    if websites.is_empty() {
+        return format!("\n{}\n", locale.label(Label::NoProductsFound));
+    }
+    let plain = colour_mode == ColourMode::Plain;
+    let (separator, rule_line, rule_separator) = match plain {
+        true => (PLAIN_COLUMN_SEPARATOR, "-", "-+-"),
+        false => (COLUMN_SEPARATOR, "─", "─┼─"),
+    };
+
+    // The text of every cell and if it has the lowest price of the row.
+    let cells = |compact: bool| -> Vec<Vec<Option<(String, bool)>>> {
+        rows.iter()
+            .map(|row| {
+                let offers: Vec<Option<Offer>> = websites
+                    .iter()
+                    .map(|website| row.products.get(website).map(|product| Offer::new(product)))
+                    .collect();
+                let best_price = offers
+                    .iter()
+                    .flatten()
+                    .filter_map(Offer::price)
+                    .fold(None, |best: Option<f32>, price| {
+                        Some(best.map_or(price, |best| best.min(price)))
+                    });
+                offers
+                    .iter()
+                    .map(|offer| {
+                        offer.as_ref().map(|offer| {
+                            let best = offer.price().is_some() && offer.price() == best_price;
+                            let mut text = offer.cell_format(compact, colour_mode, locale);
+                            // The lowest price can not be highlighted in plain mode.
+                            if best && plain {
+                                text.push_str(format!(" ({})", locale.label(Label::Best)).as_str());
+                            }
+                            (text, best)
+                        })
+                    })
+                    .collect()
+            })
+            .collect()
+    };
+    let websites_width = |cells: &Vec<Vec<Option<(String, bool)>>>| -> Vec<usize> {
+        websites
+            .iter()
+            .enumerate()
+            .map(|(index, website)| {
+                cells
+                    .iter()
+                    .filter_map(|row| row[index].as_ref())
+                    .map(|(text, _)| text.width())
+                    .chain(std::iter::once(website.to_string().width()))
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect()
+    };
+
+    // Full cells if they fit leaving enough space for the name, compact ones otherwise.
+    let separators_width = separator.width() * websites.len();
+    let mut table_cells = cells(false);
+    let mut column_widths = websites_width(&table_cells);
+    if width < MIN_PRODUCT_WIDTH + separators_width + column_widths.iter().sum::<usize>() {
+        table_cells = cells(true);
+        column_widths = websites_width(&table_cells);
+    }
+    let longest_name = rows
+        .iter()
+        .map(|row| row.name.width())
+        .chain(std::iter::once(locale.label(Label::Product).width()))
+        .max()
+        .unwrap();
+    let product_width = width
+        .saturating_sub(separators_width + column_widths.iter().sum::<usize>())
+        .clamp(MIN_PRODUCT_WIDTH.min(longest_name), longest_name);
+
+    let mut out: String = String::from("\n");
+    let header: Vec<String> = std::iter::once(pad(locale.label(Label::Product), product_width))
+        .chain(
+            websites
+                .iter()
+                .zip(column_widths.iter())
+                .map(|(website, width)| pad(&website.to_string(), *width)),
+        )
+        .collect();
+    let header = header.join(separator);
+    match plain {
+        true => out.push_str(header.trim_end()),
+        false => out.push_str(&Style::new().bold().paint(header.trim_end()).to_string()),
+    }
+    out.push('\n');
+    let rule: Vec<String> = std::iter::once(product_width)
+        .chain(column_widths.iter().copied())
+        .map(|width| rule_line.repeat(width))
+        .collect();
+    out.push_str(&rule.join(rule_separator));
+    out.push('\n');
+
+    for (row, row_cells) in rows.iter().zip(table_cells.iter()) {
+        let name = truncate(&row.name, product_width, plain);
+        let mut cells: Vec<String> = vec![pad(&name, product_width)];
+        for (cell, width) in row_cells.iter().zip(column_widths.iter()) {
+            let cell = match cell {
+                Some((text, best)) => {
+                    let padding = " ".repeat(width.saturating_sub(text.width()));
+                    match *best && !plain {
+                        true => format!("{}{padding}", Colour::Green.bold().paint(text)),
+                        false => format!("{text}{padding}"),
+                    }
+                }
+                None => " ".repeat(*width),
+            };
+            cells.push(cell);
+        }
+        out.push_str(cells.join(separator).trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+/// Returns the summary of the search, a line for every website and one for all of them.
+/// # Arguments
+/// * `summary` - The statistics of the search.
+/// * `colour_mode` - The title is bold unless in plain mode.
+/// * `locale` - The texts and the format of the prices and numbers.
+/// # Example
+/// Summary
+/// sephora.es      : 4 found, 2 filtered by similarity, 1 shown - €15.00 to €30.00, average €22.50 - 1 on sale, average -25% - 2 unavailable tones - 0 unavailable products - 1.50s
+/// maquillalia.com : 1 found, 0 filtered by similarity, 1 shown - €5.00 to €5.00, average €5.00 - 1 on sale, average -50% - 0 unavailable tones - 1 unavailable products - 0.50s
+/// Overall         : 5 found, 2 filtered by similarity, 2 shown - €5.00 to €30.00, average €16.67 - 2 on sale, average -38% - 2 unavailable tones - 1 unavailable products - 2.00s
+pub fn format_summary(summary: &Summary, colour_mode: ColourMode, locale: Locale) -> String {
+    let lines: Vec<(String, &Statistics)> = summary
+        .websites
+        .iter()
+        .map(|(website, statistics)| (website.to_string(), statistics))
+        .chain(std::iter::once((
+            String::from(locale.label(Label::Overall)),
+            &summary.overall,
+        )))
+        .collect();
+    let width = lines.iter().map(|(name, _)| name.width()).max().unwrap();
+
+    let title = locale.label(Label::Summary);
+    let mut out: String = match colour_mode {
+        ColourMode::Plain => format!("\n{title}\n"),
+        _ => format!("\n{}\n", Style::new().bold().paint(title)),
+    };
+    for (name, statistics) in lines {
+        let prices = match (
+            statistics.cheapest,
+            statistics.most_expensive,
+            statistics.average_price,
+        ) {
+            (Some(cheapest), Some(most_expensive), Some(average_price)) => format!(
+                "{} {} {}, {} {}",
+                locale.price(cheapest),
+                locale.label(Label::To),
+                locale.price(most_expensive),
+                locale.label(Label::Average),
+                locale.price(average_price)
+            ),
+            _ => format!("{} {MISSING_FIELD}", locale.label(Label::Prices)),
+        };
+        let discounts = match statistics.average_discount {
+            Some(average_discount) => format!(
+                "{} {}, {} -{}%",
+                statistics.discounts,
+                locale.label(Label::OnSale),
+                locale.label(Label::Average),
+                locale.number(average_discount, 0)
+            ),
+            None => format!("0 {}", locale.label(Label::OnSale)),
+        };
+        out.push_str(
+            format!(
+                "{} : {} {}, {} {}, {} {} - {prices} - {discounts} - {} {} - {} {} - {}s\n",
+                pad(&name, width),
+                statistics.found,
+                locale.label(Label::Found),
+                statistics.filtered_by_similarity,
+                locale.label(Label::FilteredBySimilarity),
+                statistics.shown,
+                locale.label(Label::Shown),
+                statistics.unavailable_tones,
+                locale.label(Label::UnavailableTones),
+                statistics.unavailable_products,
+                locale.label(Label::UnavailableProducts),
+                locale.number(statistics.time_seconds as f32, 2)
+            )
+            .as_str(),
+        );
+    }
+    out
+}
+
+/// Returns the text with spaces at the end up to the width provided.
+fn pad(text: &str, width: usize) -> String {
+    format!("{text}{}", " ".repeat(width.saturating_sub(text.width())))
+}
+
+/// Returns the text cut to the width provided, with an ellipsis at the end if it was cut.
+/// The ellipsis is "..." in plain mode.
+fn truncate(text: &str, width: usize, plain: bool) -> String {
+    if text.width() <= width {
+        return text.to_string();
+    }
+    let ellipsis = if plain { "..." } else { "…" };
+    let mut out: String = String::new();
+    for character in text.chars() {
+        if out.width() + character.to_string().width() + ellipsis.width() > width {
+            break;
+        }
+        out.push(character);
+    }
+    format!("{}{ellipsis}", out.trim_end())
 }
 
 #[cfg(test)]
 mod tests {
     use scrapped_webs::product::Tone;
+    use scrapped_webs::similarity::SimilarityStrategy;
 
-    use crate::parameters::Website;
+    use crate::parameters::SortingType;
 
     use super::*;
 
+    /// Creates the results used in the table tests, the first product is sold in both websites.
+    fn tear_up_table() -> ResultsByWebsite {
+        let sephora_products = vec![
+            Product::new(
+                String::from("Soft Pinch Liquid Blush"),
+                Some(String::from("Rare Beauty")),
+                String::from("http://www.sephora.es/1"),
+                None,
+                None,
+                Some(vec![
+                    Tone::new(
+                        Some(String::from("Joy")),
+                        Some(30.0),
+                        None,
+                        false,
+                        None,
+                        None,
+                    ),
+                    Tone::new(
+                        Some(String::from("Hope")),
+                        Some(26.99),
+                        Some(20.24),
+                        true,
+                        None,
+                        None,
+                    ),
+                ]),
+                None,
+                0.9,
+                true,
+            ),
+            Product::new(
+                String::from("Butter Gloss"),
+                Some(String::from("NYX")),
+                String::from("http://www.sephora.es/2"),
+                Some(8.99),
+                None,
+                None,
+                None,
+                0.5,
+                true,
+            ),
+        ];
+        let maquillalia_products = vec![Product::new(
+            String::from("Soft Pinch Liquid Blush "),
+            Some(String::from("RARE BEAUTY")),
+            String::from("http://www.maquillalia.com/1"),
+            Some(24.95),
+            None,
+            None,
+            None,
+            0.9,
+            false,
+        )];
+        let mut results_by_website = ResultsByWebsite::new();
+        results_by_website.insert(Website::SephoraSpain, sephora_products);
+        results_by_website.insert(Website::Maquillalia, maquillalia_products);
+        results_by_website
+    }
+
+    /// Tests the offer of a product with tones is the one of its effective price, even with a price not a number.
+    #[test]
+    fn offer_cheapest_tone() {
+        let mut product = tear_up_table()[&Website::SephoraSpain][0].clone();
+        let offer = Offer::new(&product);
+        assert_eq!(offer.price(), product.effective_price());
+        assert_eq!(offer.price_standard, Some(26.99));
+        assert_eq!(offer.discount_percentage, Some(25));
+        assert!(offer.available);
+        product.tones.as_mut().unwrap()[0].price_standard = Some(f32::NAN);
+        let offer = Offer::new(&product);
+        assert_eq!(offer.price(), Some(20.24));
+        assert_eq!(offer.price(), product.effective_price());
+    }
+
+    /// Tests the same product of different websites is shown in the same row.
+    #[test]
+    fn format_table_matches_products() {
+        let output = format_table(&tear_up_table(), 200, ColourMode::Ansi256, Locale::EnGb);
+        println!("{output}");
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 5);
+        assert!(lines[1].contains("Product") && lines[1].contains("sephora.es"));
+        assert!(lines[1].find("sephora.es").unwrap() < lines[1].find("maquillalia.com").unwrap());
+        assert!(lines[3].starts_with("Rare Beauty Soft Pinch Liquid Blush "));
+        assert!(lines[3].contains("✔ €26.99 → €20.24 -25%"));
+        assert!(lines[3].contains("✘ €24.95"));
+        assert!(lines[4].starts_with("NYX Butter Gloss "));
+        assert!(lines[4].contains("✔ €8.99"));
+    }
+
+    /// Tests the lowest price of every row is highlighted.
+    #[test]
+    fn format_table_highlights_best_price() {
+        let output = format_table(&tear_up_table(), 200, ColourMode::Ansi256, Locale::EnGb);
+        let lines: Vec<&str> = output.lines().collect();
+        let best_price = Colour::Green.bold().paint("✔ €26.99 → €20.24 -25%");
+        assert!(lines[3].contains(&best_price.to_string()));
+        assert!(!lines[3].contains(&Colour::Green.bold().paint("✘ €24.95").to_string()));
+    }
+
+    /// Tests the cells are shortened and the names truncated in narrow terminals.
+    #[test]
+    fn format_table_narrow_terminal() {
+        let output = format_table(&tear_up_table(), 55, ColourMode::Ansi256, Locale::EnGb);
+        println!("{output}");
+        let lines: Vec<&str> = output.lines().collect();
+        assert!(lines[3].starts_with("Rare Beauty Soft Pinch… "));
+        assert!(lines[3].contains("✔ €20.24") && !lines[3].contains("€26.99"));
+        assert!(lines[2].width() <= 55);
+    }
+
+    /// Tests the plain table only uses ASCII, without escape sequences, and marks the lowest price.
+    #[test]
+    fn format_table_plain() {
+        let output = format_table(&tear_up_table(), 200, ColourMode::Plain, Locale::EnGb);
+        println!("{output}");
+        let lines: Vec<&str> = output.lines().collect();
+        assert!(!output.contains('\u{1b}'));
+        assert!(output
+            .chars()
+            .all(|character| character.is_ascii() || character == '€'));
+        assert!(lines[1].starts_with("Product") && lines[1].contains(" | sephora.es"));
+        assert!(lines[2].contains("-+-"));
+        assert!(lines[3].contains("yes €26.99 -> €20.24 -25% (best) | no  €24.95"));
+        assert!(lines[4].contains("yes €8.99 (best)"));
+        // The columns are aligned in every line.
+        assert_eq!(lines[1].find(" | "), lines[3].find(" | "));
+
+        let output = format_table(&tear_up_table(), 55, ColourMode::Plain, Locale::EnGb);
+        assert!(output
+            .lines()
+            .nth(3)
+            .unwrap()
+            .starts_with("Rare Beauty Soft..."));
+    }
+
+    /// Tests the labels and prices of the table follow the locale.
+    #[test]
+    fn format_table_es_es() {
+        let output = format_table(&tear_up_table(), 200, ColourMode::Plain, Locale::EsEs);
+        println!("{output}");
+        let lines: Vec<&str> = output.lines().collect();
+        assert!(lines[1].starts_with("Producto"));
+        assert!(lines[3].contains("sí 26,99 € -> 20,24 € -25% (mejor) | no 24,95 €"));
+        assert_eq!(
+            format_table(
+                &ResultsByWebsite::new(),
+                80,
+                ColourMode::Plain,
+                Locale::EsEs
+            ),
+            "\nNo se han encontrado productos.\n"
+        );
+    }
+
+    /// Tests the summary has a line for every website and one for all of them.
+    #[test]
+    fn format_summary_all_websites() {
+        let mut summary = Summary::default();
+        summary.websites.insert(
+            Website::SephoraSpain,
+            Statistics {
+                found: 4,
+                filtered_by_similarity: 2,
+                shown: 1,
+                cheapest: Some(15.0),
+                most_expensive: Some(30.0),
+                average_price: Some(22.5),
+                discounts: 1,
+                average_discount: Some(25.0),
+                unavailable_tones: 2,
+                unavailable_products: 0,
+                time_seconds: 1.5,
+            },
+        );
+        summary.websites.insert(
+            Website::Maquillalia,
+            Statistics {
+                found: 3,
+                filtered_by_similarity: 3,
+                ..Default::default()
+            },
+        );
+        let output = format_summary(&summary, ColourMode::Plain, Locale::EnGb);
+        println!("{output}");
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines[1], "Summary");
+        assert_eq!(
+            lines[2],
+            "sephora.es      : 4 found, 2 filtered by similarity, 1 shown - €15.00 to €30.00, average €22.50 - 1 on sale, average -25% - 2 unavailable tones - 0 unavailable products - 1.50s"
+        );
+        assert_eq!(
+            lines[3],
+            "maquillalia.com : 3 found, 3 filtered by similarity, 0 shown - prices N/A - 0 on sale - 0 unavailable tones - 0 unavailable products - 0.00s"
+        );
+        assert!(
+            lines[4].starts_with("Overall         : 0 found, 0 filtered by similarity, 0 shown")
+        );
+
+        let output = format_summary(&summary, ColourMode::Ansi256, Locale::EsEs);
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines[1], Style::new().bold().paint("Resumen").to_string());
+        assert_eq!(
+            lines[2],
+            "sephora.es      : 4 encontrados, 2 filtrados por similitud, 1 mostrados - 15,00 € a 30,00 €, media 22,50 € - 1 en oferta, media -25% - 2 tonos agotados - 0 productos agotados - 1,50s"
+        );
+        assert!(lines[3].contains(" - precios N/A - 0 en oferta - "));
+        assert!(lines[4].starts_with("Total           : 0 encontrados"));
+    }
+
+    /// Tests the table without results.
+    #[test]
+    fn format_table_without_products() {
+        assert_eq!(
+            format_table(
+                &ResultsByWebsite::new(),
+                80,
+                ColourMode::Plain,
+                Locale::EnGb
+            ),
+            "\nNo products found.\n"
+        );
+    }
+
     /// Tests the print function.
     #[test]
-    fn print_function_happy_path() {
+    fn format_function_happy_path() {
         let tone: Tone = Tone::new(
             Some(String::from("Tone 1")),
             Some(50.99),
@@ -55,7 +749,7 @@ mod tests {
             None,
             None,
         );
-        let product: Product = Product::new(
+        let mut product: Product = Product::new(
             String::from("Product 1"),
             Some(String::from("Brand")),
             String::from("http://www.test.com"),
@@ -66,9 +760,146 @@ mod tests {
             0.92,
             true,
         );
+        product.similarity_explanation = Some(similarity::explain(
+            "Brand Product 1",
+            &[String::from("Product")],
+            SimilarityStrategy::FullName,
+        ));
 
         let mut results_by_websites: ResultsByWebsite = ResultsByWebsite::new();
         results_by_websites.insert(Website::SephoraSpain, vec![product]);
-        print(&results_by_websites);
+        let output = format(
+            &results_by_websites,
+            &SortBy::from(SortingType::Similarity),
+            ColourMode::Plain,
+            Locale::EnGb,
+            true,
+        );
+        println!("{output}");
+        assert!(output.starts_with("\n- 92.00%. Product 1 - Brand - "));
+    }
+
+    /// Tests the plain output only uses ASCII, except the euro sign of the prices, explanations included.
+    #[test]
+    fn format_plain_ascii() {
+        let mut product = Product::new(
+            String::from("Product 1"),
+            Some(String::from("Brand")),
+            String::from("http://www.test.com"),
+            None,
+            None,
+            Some(vec![
+                Tone::new(
+                    Some(String::from("Nude")),
+                    Some(50.99),
+                    Some(20.0),
+                    true,
+                    None,
+                    Some(9.0),
+                ),
+                Tone::new(
+                    Some(String::from("Red")),
+                    Some(50.99),
+                    None,
+                    false,
+                    None,
+                    None,
+                ),
+            ]),
+            Some(9.5),
+            0.92,
+            true,
+        );
+        product.similarity_explanation = Some(similarity::explain(
+            "Brand Product 1",
+            &[String::from("Product rosa")],
+            SimilarityStrategy::FullName,
+        ));
+        let results_by_websites = ResultsByWebsite::from([(Website::SephoraSpain, vec![product])]);
+        let output = format(
+            &results_by_websites,
+            &SortBy::from(SortingType::Similarity),
+            ColourMode::Plain,
+            Locale::EnGb,
+            true,
+        );
+        println!("{output}");
+        assert!(output
+            .chars()
+            .all(|character| character.is_ascii() || character == '€'));
+        assert!(output.contains("tokens: product->product 100% | rosa->"));
+        assert!(output.contains("was €50.99 now €20.00"));
+    }
+
+    /// Tests the products of every website are printed in a single sorted list.
+    #[test]
+    fn format_sorted_across_websites() {
+        let product = |name: &str, price: f32| Product {
+            name: String::from(name),
+            price_standard: Some(price),
+            ..Default::default()
+        };
+        let results_by_websites = ResultsByWebsite::from([
+            (
+                Website::SephoraSpain,
+                vec![product("Cheap", 5.0), product("Expensive", 30.0)],
+            ),
+            (Website::Maquillalia, vec![product("Middle", 15.0)]),
+        ]);
+        let output = format(
+            &results_by_websites,
+            &SortBy::from(SortingType::Price),
+            ColourMode::Plain,
+            Locale::EnGb,
+            false,
+        );
+        let position = |name: &str| output.find(name).unwrap();
+        assert!(position("Cheap") < position("Middle"));
+        assert!(position("Middle") < position("Expensive"));
+    }
+
+    /// Tests products without any of the optional fields are rendered with placeholders.
+    #[test]
+    fn format_all_none_products() {
+        let mut results_by_websites: ResultsByWebsite = ResultsByWebsite::new();
+        results_by_websites.insert(
+            Website::SephoraSpain,
+            vec![
+                Product::default(),
+                Product {
+                    tones: Some(vec![Tone::default()]),
+                    ..Default::default()
+                },
+            ],
+        );
+        results_by_websites.insert(Website::Maquillalia, vec![Product::default()]);
+
+        let output = format(
+            &results_by_websites,
+            &SortBy::from(SortingType::Similarity),
+            ColourMode::TrueColour,
+            Locale::EnGb,
+            false,
+        );
+        assert!(output.contains("- 0.00%.  - N/A - N/A: "));
+        assert!(output.contains("    - ❌   N/A - N/A"));
+
+        let dimmed = format(
+            &results_by_websites,
+            &SortBy::from(SortingType::Similarity),
+            ColourMode::TrueColour,
+            Locale::EnGb,
+            true,
+        );
+        assert!(dimmed.contains(
+            &Style::new()
+                .dimmed()
+                .paint("    - ❌   N/A - N/A")
+                .to_string()
+        ));
+
+        let table = format_table(&results_by_websites, 80, ColourMode::Ansi256, Locale::EnGb);
+        assert!(table.contains("✘ N/A"));
+        assert!(!table.contains(&Colour::Green.bold().paint("✘ N/A").to_string()));
     }
 }
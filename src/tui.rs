@@ -0,0 +1,607 @@
+//! This file handles the interactive terminal UI to browse the results without searching again.
+
+use std::collections::HashSet;
+
+use clap::ArgEnum;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span, Text};
+use ratatui::widgets::{Block, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+use scrapped_webs::locale::Locale;
+use scrapped_webs::product::{Product, MISSING_FIELD};
+
+use crate::parameters::{SortBy, SortingType, Website};
+use crate::scraper_handler::ResultsByWebsite;
+use crate::sorting;
+use crate::terminal_visualizer::{availability_mark, Offer};
+
+/// State of the interactive UI.
+pub struct App<'a> {
+    /// Every product found with its website.
+    products: Vec<(&'a Website, &'a Product)>,
+    /// Current sorting of the list.
+    sort_by: SortBy,
+    /// The format of the prices.
+    locale: Locale,
+    /// Text the brand or name of the products must contain.
+    filter: String,
+    /// True while the user is typing the filter.
+    editing_filter: bool,
+    /// Show only the products with any tone available.
+    available_only: bool,
+    /// Show only the products with any tone on sale.
+    on_sale_only: bool,
+    /// Links of the products marked as favourites.
+    favourites: HashSet<&'a str>,
+    /// Selected product of the list.
+    state: ListState,
+    /// True when the user asked to exit.
+    exit: bool,
+}
+
+impl<'a> App<'a> {
+    /// Creates the UI state with the results of the search.
+    /// # Arguments
+    /// * `results_by_website` - The products found for every website.
+    /// * `sort_by` - The initial sorting of the list.
+    /// * `locale` - The format of the prices.
+    pub fn new(results_by_website: &'a ResultsByWebsite, sort_by: &SortBy, locale: Locale) -> Self {
+        let mut app = Self {
+            products: sorting::merge(results_by_website, sort_by),
+            sort_by: sort_by.clone(),
+            locale,
+            filter: String::new(),
+            editing_filter: false,
+            available_only: false,
+            on_sale_only: false,
+            favourites: HashSet::new(),
+            state: ListState::default(),
+            exit: false,
+        };
+        app.clamp_selection();
+        app
+    }
+
+    /// Runs the UI until the user exits.
+    pub fn run(&mut self, terminal: &mut DefaultTerminal) -> Result<(), anyhow::Error> {
+        while !self.exit {
+            terminal.draw(|frame| self.draw(frame))?;
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press {
+                    self.handle_key(key);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the products to show, filtered, in the current order.
+    fn visible(&self) -> Vec<(&'a Website, &'a Product)> {
+        let filter = scrapped_webs::similarity::normalize(&self.filter);
+        self.products
+            .iter()
+            .filter(|(_, product)| {
+                let text = scrapped_webs::similarity::normalize(
+                    format!(
+                        "{} {}",
+                        product.brand.as_deref().unwrap_or_default(),
+                        product.name
+                    )
+                    .as_str(),
+                );
+                text.contains(&filter)
+            })
+            .filter(|(_, product)| !self.available_only || Offer::new(product).available)
+            .filter(|(_, product)| !self.on_sale_only || on_sale(product))
+            .copied()
+            .collect()
+    }
+
+    /// Sorts the products by the current sorting, as in the CLI.
+    fn sort(&mut self) {
+        let sort_by = &self.sort_by;
+        self.products
+            .sort_by(|(website1, product1), (website2, product2)| {
+                sorting::compare(product1, product2, sort_by).then_with(|| website1.cmp(website2))
+            });
+    }
+
+    /// Keeps the selection inside the visible products.
+    fn clamp_selection(&mut self) {
+        let visible = self.visible().len();
+        match (visible, self.state.selected()) {
+            (0, _) => self.state.select(None),
+            (_, Some(selected)) => self.state.select(Some(selected.min(visible - 1))),
+            (_, None) => self.state.select(Some(0)),
+        }
+    }
+
+    /// Returns the selected product.
+    fn selected(&self) -> Option<(&'a Website, &'a Product)> {
+        self.state
+            .selected()
+            .and_then(|selected| self.visible().get(selected).copied())
+    }
+
+    /// Updates the state with a key pressed by the user.
+    fn handle_key(&mut self, key: KeyEvent) {
+        if self.editing_filter {
+            match key.code {
+                KeyCode::Enter | KeyCode::Esc => self.editing_filter = false,
+                KeyCode::Backspace => {
+                    self.filter.pop();
+                }
+                KeyCode::Char(character) => self.filter.push(character),
+                _ => {}
+            }
+            self.clamp_selection();
+            return;
+        }
+
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => self.exit = true,
+            KeyCode::Down | KeyCode::Char('j') => self.state.select_next(),
+            KeyCode::Up | KeyCode::Char('k') => self.state.select_previous(),
+            KeyCode::Char('s') => {
+                // Cycles through the criteria in their usual direction.
+                let variants = SortingType::value_variants();
+                let position = variants
+                    .iter()
+                    .position(|variant| variant == self.sort_by.primary())
+                    .unwrap_or_default();
+                self.sort_by = SortBy::from(variants[(position + 1) % variants.len()].clone());
+                self.sort();
+            }
+            KeyCode::Char('/') => self.editing_filter = true,
+            KeyCode::Char('a') => self.available_only = !self.available_only,
+            KeyCode::Char('o') => self.on_sale_only = !self.on_sale_only,
+            KeyCode::Char('f') | KeyCode::Char(' ') => {
+                if let Some((_, product)) = self.selected() {
+                    if !self.favourites.remove(product.link.as_str()) {
+                        self.favourites.insert(product.link.as_str());
+                    }
+                }
+            }
+            _ => {}
+        }
+        self.clamp_selection();
+    }
+
+    /// Draws the list of products, the detail of the selected one and the status bar.
+    fn draw(&mut self, frame: &mut Frame) {
+        let [main_area, status_area] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+        let [list_area, detail_area] =
+            Layout::horizontal([Constraint::Percentage(45), Constraint::Percentage(55)])
+                .areas(main_area);
+
+        let visible = self.visible();
+        let items: Vec<ListItem> = visible
+            .iter()
+            .map(|(_, product)| {
+                let favourite = match self.favourites.contains(product.link.as_str()) {
+                    true => "★ ",
+                    false => "  ",
+                };
+                let price = Offer::new(product)
+                    .price()
+                    .map_or(String::from(MISSING_FIELD), |price| {
+                        self.locale.price(price)
+                    });
+                ListItem::new(format!(
+                    "{favourite}{:>3.0}% {} {} - {price}",
+                    product.similarity * 100.0,
+                    product.brand.as_deref().unwrap_or_default().trim(),
+                    product.name.trim()
+                ))
+            })
+            .collect();
+        let list = List::new(items)
+            .block(Block::bordered().title(format!(
+                " Results {}/{} - sorted by {} ",
+                visible.len(),
+                self.products.len(),
+                self.sort_by
+            )))
+            .highlight_style(Style::new().add_modifier(Modifier::REVERSED))
+            .highlight_symbol("> ");
+        frame.render_stateful_widget(list, list_area, &mut self.state);
+
+        let detail = match self.selected() {
+            Some((website, product)) => self.detail(website, product),
+            None => Text::from("No products match the filters."),
+        };
+        frame.render_widget(
+            Paragraph::new(detail)
+                .block(Block::bordered().title(" Detail "))
+                .wrap(Wrap { trim: false }),
+            detail_area,
+        );
+
+        frame.render_widget(Paragraph::new(self.status()), status_area);
+    }
+
+    /// Returns the detail of a product: prices, rating, availability, link and tones.
+    fn detail(&self, website: &Website, product: &Product) -> Text<'static> {
+        let mut lines: Vec<Line> = vec![
+            Line::from(Span::styled(
+                product.name.trim().to_string(),
+                Style::new().add_modifier(Modifier::BOLD),
+            )),
+            Line::from(format!(
+                "Brand: {}",
+                product.brand.as_deref().unwrap_or(MISSING_FIELD).trim()
+            )),
+            Line::from(format!("Website: {website}")),
+            Line::from(format!("Similarity: {:.2}%", product.similarity * 100.0)),
+        ];
+        if let Some(rating) = product.rating {
+            lines.push(Line::from(format!("Rating: {rating}⭐")));
+        }
+        if product.tones.is_none() {
+            lines.push(Line::from(format!(
+                "Price: {}",
+                price_format(&Offer::new(product), self.locale)
+            )));
+            lines.push(availability_line(product.available, String::new()));
+        }
+        if self.favourites.contains(product.link.as_str()) {
+            lines.push(Line::from(Span::styled(
+                "★ Favourite",
+                Style::new().fg(Color::Yellow),
+            )));
+        }
+        lines.push(Line::from(format!("Link: {}", product.link)));
+
+        if let Some(tones) = product.tones.as_ref() {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                format!("Tones ({})", tones.len()),
+                Style::new().add_modifier(Modifier::BOLD),
+            )));
+            for tone in tones {
+                let mut text = format!(
+                    "{} - {}",
+                    tone.name.as_deref().unwrap_or(MISSING_FIELD),
+                    price_format(&Offer::from(tone), self.locale)
+                );
+                if let Some(rating) = tone.rating {
+                    text.push_str(format!(" - {rating}⭐").as_str());
+                }
+                lines.push(availability_line(tone.available, text));
+            }
+        }
+        Text::from(lines)
+    }
+
+    /// Returns the status bar with the filters applied and the keys available.
+    fn status(&self) -> Line<'static> {
+        let filter = match self.editing_filter {
+            true => format!("Filter: {}▏ (Enter to finish)", self.filter),
+            false if self.filter.is_empty() => String::from("/ filter"),
+            false => format!("/ filter: {}", self.filter),
+        };
+        let toggle = |enabled: bool| match enabled {
+            true => "on",
+            false => "off",
+        };
+        Line::from(format!(
+            " {filter} · s sort · a available only: {} · o on sale only: {} · f favourite · q quit",
+            toggle(self.available_only),
+            toggle(self.on_sale_only)
+        ))
+    }
+}
+
+/// Shows the results in the interactive UI until the user exits.
+/// # Arguments
+/// * `results_by_website` - The products found for every website.
+/// * `sort_by` - The initial sorting of the list.
+/// * `locale` - The format of the prices.
+pub fn run(
+    results_by_website: &ResultsByWebsite,
+    sort_by: &SortBy,
+    locale: Locale,
+) -> Result<(), anyhow::Error> {
+    let mut terminal = ratatui::init();
+    let result = App::new(results_by_website, sort_by, locale).run(&mut terminal);
+    ratatui::restore();
+    result
+}
+
+/// Returns true if the product or any of its tones is on sale.
+fn on_sale(product: &Product) -> bool {
+    match product.tones.as_ref() {
+        Some(tones) => tones
+            .iter()
+            .any(|tone| tone.discount_percentage().is_some()),
+        None => product.discount_percentage().is_some(),
+    }
+}
+
+/// Returns the price, with the standard one and the discount if it is on sale.
+fn price_format(offer: &Offer, locale: Locale) -> String {
+    offer.price_format(
+        locale,
+        |price_standard, price_sales, discount_percentage| {
+            format!("{price_standard} → {price_sales} -{discount_percentage}%")
+        },
+        str::to_string,
+    )
+}
+
+/// Returns the line with the availability mark before the text.
+fn availability_line(available: bool, text: String) -> Line<'static> {
+    let colour = match available {
+        true => Color::Green,
+        false => Color::Red,
+    };
+    Line::from(vec![
+        Span::styled(
+            format!("{} ", availability_mark(available)),
+            Style::new().fg(colour),
+        ),
+        Span::raw(text),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::backend::TestBackend;
+    use ratatui::crossterm::event::KeyModifiers;
+    use ratatui::Terminal;
+    use scrapped_webs::product::Tone;
+
+    use super::*;
+
+    /// Creates the results used in the tests.
+    fn tear_up() -> ResultsByWebsite {
+        let blush = Product::new(
+            String::from("Soft Pinch Liquid Blush"),
+            Some(String::from("Rare Beauty")),
+            String::from("http://www.sephora.es/1"),
+            None,
+            None,
+            Some(vec![
+                Tone::new(
+                    Some(String::from("Joy")),
+                    Some(26.99),
+                    Some(20.24),
+                    true,
+                    None,
+                    None,
+                ),
+                Tone::new(
+                    Some(String::from("Hope")),
+                    Some(26.99),
+                    None,
+                    false,
+                    None,
+                    None,
+                ),
+            ]),
+            Some(9.5),
+            0.9,
+            true,
+        );
+        let gloss = Product::new(
+            String::from("Butter Gloss"),
+            Some(String::from("NYX")),
+            String::from("http://www.sephora.es/2"),
+            Some(8.99),
+            None,
+            None,
+            Some(7.0),
+            0.5,
+            false,
+        );
+        let highlighter = Product::new(
+            String::from("Iluminador Líquido"),
+            Some(String::from("Catrice")),
+            String::from("http://www.maquillalia.com/1"),
+            Some(6.0),
+            Some(4.5),
+            None,
+            None,
+            0.7,
+            true,
+        );
+        let mut results_by_website = ResultsByWebsite::new();
+        results_by_website.insert(Website::SephoraSpain, vec![blush, gloss]);
+        results_by_website.insert(Website::Maquillalia, vec![highlighter]);
+        results_by_website
+    }
+
+    /// Returns the names of the visible products in order.
+    fn names(app: &App) -> Vec<String> {
+        app.visible()
+            .iter()
+            .map(|(_, product)| product.name.clone())
+            .collect()
+    }
+
+    /// Presses a key in the UI.
+    fn press(app: &mut App, code: KeyCode) {
+        app.handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    /// Tests the products are sorted again in every sorting type.
+    #[test]
+    fn sort_all_types() {
+        let results_by_website = tear_up();
+        let mut app = App::new(
+            &results_by_website,
+            &SortBy::from(SortingType::Similarity),
+            Locale::EnGb,
+        );
+        assert_eq!(
+            names(&app),
+            vec![
+                "Soft Pinch Liquid Blush",
+                "Iluminador Líquido",
+                "Butter Gloss"
+            ]
+        );
+        press(&mut app, KeyCode::Char('s'));
+        assert_eq!(app.sort_by.primary(), &SortingType::Brand);
+        assert_eq!(
+            names(&app),
+            vec![
+                "Iluminador Líquido",
+                "Butter Gloss",
+                "Soft Pinch Liquid Blush"
+            ]
+        );
+        press(&mut app, KeyCode::Char('s'));
+        assert_eq!(
+            names(&app),
+            vec![
+                "Soft Pinch Liquid Blush",
+                "Butter Gloss",
+                "Iluminador Líquido"
+            ]
+        );
+        press(&mut app, KeyCode::Char('s'));
+        assert_eq!(app.sort_by.primary(), &SortingType::Name);
+        assert_eq!(
+            names(&app),
+            vec![
+                "Butter Gloss",
+                "Iluminador Líquido",
+                "Soft Pinch Liquid Blush"
+            ]
+        );
+        press(&mut app, KeyCode::Char('s'));
+        assert_eq!(app.sort_by.primary(), &SortingType::Price);
+        assert_eq!(
+            names(&app),
+            vec![
+                "Iluminador Líquido",
+                "Butter Gloss",
+                "Soft Pinch Liquid Blush"
+            ]
+        );
+    }
+
+    /// Tests the text filter and the available and on sale toggles.
+    #[test]
+    fn filters() {
+        let results_by_website = tear_up();
+        let mut app = App::new(
+            &results_by_website,
+            &SortBy::from(SortingType::Similarity),
+            Locale::EnGb,
+        );
+        press(&mut app, KeyCode::Char('/'));
+        for character in "liqu".chars() {
+            press(&mut app, KeyCode::Char(character));
+        }
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(
+            names(&app),
+            vec!["Soft Pinch Liquid Blush", "Iluminador Líquido"]
+        );
+        // Keys are not captured by the filter any more.
+        press(&mut app, KeyCode::Char('a'));
+        assert!(app.available_only);
+        assert_eq!(app.filter, "liqu");
+
+        app.filter.clear();
+        assert_eq!(
+            names(&app),
+            vec!["Soft Pinch Liquid Blush", "Iluminador Líquido"]
+        );
+        press(&mut app, KeyCode::Char('a'));
+        press(&mut app, KeyCode::Char('o'));
+        assert_eq!(
+            names(&app),
+            vec!["Soft Pinch Liquid Blush", "Iluminador Líquido"]
+        );
+
+        app.filter = String::from("nothing");
+        app.clamp_selection();
+        assert_eq!(app.selected(), None);
+    }
+
+    /// Tests the selection and favourites.
+    #[test]
+    fn favourites() {
+        let results_by_website = tear_up();
+        let mut app = App::new(
+            &results_by_website,
+            &SortBy::from(SortingType::Similarity),
+            Locale::EnGb,
+        );
+        assert_eq!(app.state.selected(), Some(0));
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Char('f'));
+        assert!(app.favourites.contains("http://www.maquillalia.com/1"));
+        press(&mut app, KeyCode::Char('f'));
+        assert!(app.favourites.is_empty());
+        press(&mut app, KeyCode::Char(' '));
+        press(&mut app, KeyCode::Char('q'));
+        assert!(app.exit);
+        assert_eq!(app.favourites.len(), 1);
+    }
+
+    /// Tests the list and the detail of the selected product are drawn.
+    #[test]
+    fn draw_list_and_detail() {
+        let results_by_website = tear_up();
+        let mut app = App::new(
+            &results_by_website,
+            &SortBy::from(SortingType::Similarity),
+            Locale::EnGb,
+        );
+        press(&mut app, KeyCode::Char('f'));
+        let mut terminal = Terminal::new(TestBackend::new(120, 20)).unwrap();
+        terminal.draw(|frame| app.draw(frame)).unwrap();
+        let screen: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(screen.contains("Results 3/3 - sorted by similarity:desc"));
+        assert!(screen.contains("> ★  90% Rare Beauty Soft Pinch Liquid Blush - €20.2"));
+        assert!(screen.contains("70% Catrice Iluminador Líquido - €4.50"));
+        assert!(screen.contains("★ Favourite"));
+        assert!(screen.contains("Tones (2)"));
+        assert!(screen.contains("Joy - €26.99 → €20.24 -25%"));
+        assert!(screen.contains("q quit"));
+    }
+
+    /// Tests products without any of the optional fields are drawn with placeholders.
+    #[test]
+    fn draw_all_none_products() {
+        let mut results_by_website = ResultsByWebsite::new();
+        results_by_website.insert(
+            Website::SephoraSpain,
+            vec![Product {
+                tones: Some(vec![Tone::default()]),
+                ..Default::default()
+            }],
+        );
+        let mut app = App::new(
+            &results_by_website,
+            &SortBy::from(SortingType::Price),
+            Locale::EnGb,
+        );
+        press(&mut app, KeyCode::Char('s'));
+        let mut terminal = Terminal::new(TestBackend::new(120, 20)).unwrap();
+        terminal.draw(|frame| app.draw(frame)).unwrap();
+        let screen: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(screen.contains("0%   - N/A"));
+        assert!(screen.contains("Brand: N/A"));
+        assert!(screen.contains("N/A - N/A"));
+    }
+}